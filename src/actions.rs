@@ -0,0 +1,505 @@
+use crate::core::MAYLIB;
+use crate::error::Error;
+use crate::gamepad;
+use crate::types::{AxisBindings, GamepadAxis, InputBinding, Keycode, Maylib, MouseButton};
+use sdl2::event::Event;
+use std::fmt::Write;
+
+/// add something that triggers an action, making the action if it doesn't exist yet
+pub fn bind_action(action: &str, binding: InputBinding) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    let bindings = get.actions.entry(action.to_string()).or_default();
+    if !bindings.contains(&binding) {
+        bindings.push(binding);
+    }
+}
+
+/// stop something from triggering an action. The action stays, even with nothing bound
+pub fn unbind_action(action: &str, binding: InputBinding) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    if let Some(bindings) = get.actions.get_mut(action) {
+        bindings.retain(|bound| *bound != binding);
+    }
+}
+
+/// remove an action and everything bound to it
+pub fn clear_action(action: &str) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.actions.remove(action);
+}
+
+/// get everything that triggers an action, in the order they were bound
+pub fn get_action_bindings(action: &str) -> Vec<InputBinding> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.actions.get(action).cloned().unwrap_or_default()
+}
+
+/// check if an action was triggered this frame.
+/// Panics if no action has this name or no window is open, see `try_is_action_pressed`
+pub fn is_action_pressed(action: &str) -> bool {
+    try_is_action_pressed(action).expect("Can't read action")
+}
+
+pub fn try_is_action_pressed(action: &str) -> Result<bool, Error> {
+    let (down, was_down) = action_state(action)?;
+    Ok(down && !was_down)
+}
+
+/// check if anything bound to an action is held down
+pub fn is_action_down(action: &str) -> bool {
+    try_is_action_down(action).expect("Can't read action")
+}
+
+pub fn try_is_action_down(action: &str) -> Result<bool, Error> {
+    Ok(action_state(action)?.0)
+}
+
+/// check if an action stopped being held this frame
+pub fn is_action_released(action: &str) -> bool {
+    try_is_action_released(action).expect("Can't read action")
+}
+
+pub fn try_is_action_released(action: &str) -> Result<bool, Error> {
+    let (down, was_down) = action_state(action)?;
+    Ok(!down && was_down)
+}
+
+/// check if nothing bound to an action is held down
+pub fn is_action_up(action: &str) -> bool {
+    try_is_action_up(action).expect("Can't read action")
+}
+
+pub fn try_is_action_up(action: &str) -> Result<bool, Error> {
+    Ok(!action_state(action)?.0)
+}
+
+/// make an axis go to -1 while `negative` is held and 1 while `positive` is held,
+/// making the axis if it doesn't exist yet
+pub fn bind_axis_keys(axis: &str, negative: InputBinding, positive: InputBinding) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    let bindings = get.axes.entry(axis.to_string()).or_default();
+    if !bindings.negative.contains(&negative) {
+        bindings.negative.push(negative);
+    }
+    if !bindings.positive.contains(&positive) {
+        bindings.positive.push(positive);
+    }
+}
+
+/// make an axis follow a stick or trigger on any connected gamepad, making the axis if it doesn't exist yet
+pub fn bind_axis_gamepad(axis: &str, gamepad_axis: GamepadAxis) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    let bindings = get.axes.entry(axis.to_string()).or_default();
+    if !bindings.gamepad.contains(&gamepad_axis) {
+        bindings.gamepad.push(gamepad_axis);
+    }
+}
+
+/// remove an axis and everything bound to it
+pub fn clear_axis(axis: &str) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.axes.remove(axis);
+}
+
+/// get an axis from -1 to 1. When keys and a gamepad both move it, the one moving it further wins
+pub fn get_axis_value(axis: &str) -> f32 {
+    try_get_axis_value(axis).expect("Can't read axis")
+}
+
+pub fn try_get_axis_value(axis: &str) -> Result<f32, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let bindings = get
+        .axes
+        .get(axis)
+        .ok_or_else(|| Error::InvalidAction(axis.to_string()))?;
+    let any_down = |bindings: &[InputBinding]| -> Result<bool, Error> {
+        for binding in bindings {
+            if binding_state(&get, *binding)?.0 {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    };
+    let keys = any_down(&bindings.positive)? as i32 - any_down(&bindings.negative)? as i32;
+    let stick = bindings
+        .gamepad
+        .iter()
+        .flat_map(|axis| {
+            get.gamepads
+                .iter()
+                .flatten()
+                .map(|gamepad| gamepad::axis_movement(&get, gamepad, *axis))
+        })
+        .fold(0.0f32, |furthest, value| {
+            if value.abs() > furthest.abs() { value } else { furthest }
+        });
+    if stick.abs() > keys.abs() as f32 {
+        Ok(stick)
+    } else {
+        Ok(keys as f32)
+    }
+}
+
+/// whether any of an action's bindings is down this frame, and whether any was last frame
+fn action_state(action: &str) -> Result<(bool, bool), Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let bindings = get
+        .actions
+        .get(action)
+        .ok_or_else(|| Error::InvalidAction(action.to_string()))?;
+    let mut state = (false, false);
+    for binding in bindings {
+        let (down, was_down) = binding_state(&get, *binding)?;
+        state.0 |= down;
+        state.1 |= was_down;
+    }
+    Ok(state)
+}
+
+/// whether a binding is down this frame and last frame. Keys and the mouse are read from the current window
+fn binding_state(get: &Maylib, binding: InputBinding) -> Result<(bool, bool), Error> {
+    let window = get
+        .windows
+        .get(&get.current_window)
+        .ok_or(Error::NoWindow)?;
+    let input = &window.input;
+    Ok(match binding {
+        InputBinding::Key(keycode) => match keycode.scancode() {
+            // Keys only count while the window has focus, like `is_key_down`
+            Some(scancode) if window.focused => (
+                input.keys_down.contains(&scancode),
+                input.previous_keys_down.contains(&scancode),
+            ),
+            _ => (false, false),
+        },
+        InputBinding::Mouse(button) => {
+            let button = button.into();
+            (
+                input.buttons_down.contains(&button),
+                input.previous_buttons_down.contains(&button),
+            )
+        }
+        InputBinding::Gamepad(button) => get.gamepads.iter().flatten().fold(
+            (false, false),
+            |(down, was_down), gamepad| {
+                (
+                    down || gamepad.buttons_down.contains(&button),
+                    was_down || gamepad.previous_buttons_down.contains(&button),
+                )
+            },
+        ),
+    })
+}
+
+/// start waiting for the next key, mouse button or gamepad button to be pressed, for rebinding menus.
+/// Get it with `get_listened_input` once it happens
+pub fn listen_for_input() {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.listening_for_input = true;
+    get.listened_input = None;
+}
+
+/// stop waiting for input without taking anything
+pub fn stop_listening_for_input() {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.listening_for_input = false;
+    get.listened_input = None;
+}
+
+/// check if `listen_for_input` is still waiting for something to be pressed
+pub fn is_listening_for_input() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.listening_for_input
+}
+
+/// take what was pressed after `listen_for_input`, or `None` if nothing has been pressed yet
+pub fn get_listened_input() -> Option<InputBinding> {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.listened_input.take()
+}
+
+/// catch the first press while listening for input, called by `begin_drawing`
+pub(crate) fn record_event(get: &mut Maylib, event: &Event) {
+    if !get.listening_for_input {
+        return;
+    }
+    let binding = match *event {
+        Event::KeyDown { keycode: Some(keycode), repeat: false, .. } => {
+            let keycode = Keycode::from(keycode);
+            if keycode == Keycode::Unknown {
+                return;
+            }
+            InputBinding::Key(keycode)
+        }
+        Event::MouseButtonDown { mouse_btn, .. } => match MouseButton::from_sdl(mouse_btn) {
+            Some(button) => InputBinding::Mouse(button),
+            None => return,
+        },
+        Event::ControllerButtonDown { button, .. } => InputBinding::Gamepad(button.into()),
+        _ => return,
+    };
+    get.listening_for_input = false;
+    get.listened_input = Some(binding);
+}
+
+/// replace every action and axis with the ones in a bindings file written by `save_bindings`.
+/// See `save_bindings` for what the file can contain
+pub fn load_bindings(path: &str) {
+    try_load_bindings(path).expect("Can't load bindings");
+}
+
+pub fn try_load_bindings(path: &str) -> Result<(), Error> {
+    let text = std::fs::read_to_string(path)?;
+    let (actions, axes) = parse_bindings(&text)?;
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.actions = actions.into_iter().collect();
+    get.axes = axes.into_iter().collect();
+    Ok(())
+}
+
+/// save every action and axis to a bindings file. This is maylib's own line-based format, not TOML,
+/// so files written by TOML tools may not load. The whole grammar is:
+///
+/// ```text
+/// # Comments start with # and run to the end of the line
+/// [actions]
+/// jump = ["key:Space", "gamepad:a"]
+///
+/// [axes.move_x]
+/// negative = ["key:A"]
+/// positive = ["key:D"]
+/// gamepad = ["leftx"]
+/// ```
+///
+/// - Each line is blank, a comment, a header or a `name = [...]` entry, with an optional comment after it
+/// - Headers are `[actions]`, or `[axes.name]` for one axis. Entries belong to the header above them
+/// - Under `[axes.name]` the entries are `negative`, `positive` and `gamepad`
+/// - Names are letters, digits, `_` and `-`, or a double quoted string
+/// - Lists are `[` and `]` around double quoted strings separated by `,`, all on one line. A last `,` is allowed
+/// - Strings can use `\"`, `\\`, `\n` and `\t` escapes, and nothing else
+/// - Inputs are named as `InputBinding::name` gives them, and gamepad axes by SDL's names,
+///   like `leftx` or `righttrigger`
+pub fn save_bindings(path: &str) {
+    try_save_bindings(path).expect("Can't save bindings");
+}
+
+pub fn try_save_bindings(path: &str) -> Result<(), Error> {
+    let text = {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        write_bindings(&get)
+    };
+    std::fs::write(path, text)?;
+    Ok(())
+}
+
+fn write_bindings(get: &Maylib) -> String {
+    let list = |names: Vec<String>| {
+        let quoted: Vec<String> = names.iter().map(|name| quote(name)).collect();
+        format!("[{}]", quoted.join(", "))
+    };
+    let binding_names = |bindings: &[InputBinding]| bindings.iter().map(|b| b.name()).collect();
+    // Sort so saving the same bindings always gives the same file
+    let mut actions: Vec<_> = get.actions.iter().collect();
+    actions.sort_by_key(|(name, _)| *name);
+    let mut axes: Vec<_> = get.axes.iter().collect();
+    axes.sort_by_key(|(name, _)| *name);
+
+    let mut text = String::from("[actions]\n");
+    for (name, bindings) in actions {
+        let _ = writeln!(text, "{} = {}", key(name), list(binding_names(bindings)));
+    }
+    for (name, bindings) in axes {
+        let gamepad = bindings
+            .gamepad
+            .iter()
+            .map(|axis| sdl2::controller::Axis::from(*axis).string())
+            .collect();
+        let _ = writeln!(text, "\n[axes.{}]", key(name));
+        let _ = writeln!(text, "negative = {}", list(binding_names(&bindings.negative)));
+        let _ = writeln!(text, "positive = {}", list(binding_names(&bindings.positive)));
+        let _ = writeln!(text, "gamepad = {}", list(gamepad));
+    }
+    text
+}
+
+/// A name, quoted unless it is a plain word
+fn key(name: &str) -> String {
+    let bare = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare { name.to_string() } else { quote(name) }
+}
+
+fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+type ParsedBindings = (Vec<(String, Vec<InputBinding>)>, Vec<(String, AxisBindings)>);
+
+/// read the bindings file grammar described on `save_bindings`: `[actions]` and `[axes.name]`
+/// tables holding arrays of strings
+fn parse_bindings(text: &str) -> Result<ParsedBindings, Error> {
+    enum Table {
+        None,
+        Actions,
+        Axis(usize),
+    }
+    let mut actions = Vec::new();
+    let mut axes: Vec<(String, AxisBindings)> = Vec::new();
+    let mut table = Table::None;
+    for (number, line) in text.lines().enumerate() {
+        let error = |message: &str| Error::Decode(format!("bindings line {}: {}", number + 1, message));
+        let mut line = Line(line.trim());
+        if line.at_end() {
+            continue;
+        }
+        if line.eat('[') {
+            let name = line.key().ok_or_else(|| error("expected a table name"))?;
+            table = match name.as_str() {
+                "actions" => Table::Actions,
+                "axes" if line.eat('.') => {
+                    let axis = line.key().ok_or_else(|| error("expected an axis name"))?;
+                    axes.push((axis, AxisBindings::default()));
+                    Table::Axis(axes.len() - 1)
+                }
+                _ => return Err(error(&format!("unknown table {}", name))),
+            };
+            if !line.eat(']') || !line.at_end() {
+                return Err(error("expected ] after the table name"));
+            }
+            continue;
+        }
+        let name = line.key().ok_or_else(|| error("expected a name"))?;
+        if !line.eat('=') {
+            return Err(error("expected = after the name"));
+        }
+        let values = line.strings().ok_or_else(|| error("expected a list of strings"))?;
+        if !line.at_end() {
+            return Err(error("unexpected text after the list"));
+        }
+        let bindings = || {
+            values
+                .iter()
+                .map(|value| {
+                    InputBinding::from_name(value)
+                        .ok_or_else(|| error(&format!("unknown input {}", value)))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        match table {
+            Table::None => return Err(error("expected [actions] or [axes.name] first")),
+            Table::Actions => actions.push((name, bindings()?)),
+            Table::Axis(index) => {
+                let axis = &mut axes[index].1;
+                match name.as_str() {
+                    "negative" => axis.negative = bindings()?,
+                    "positive" => axis.positive = bindings()?,
+                    "gamepad" => {
+                        axis.gamepad = values
+                            .iter()
+                            .map(|value| {
+                                sdl2::controller::Axis::from_string(value)
+                                    .map(GamepadAxis::from)
+                                    .ok_or_else(|| error(&format!("unknown gamepad axis {}", value)))
+                            })
+                            .collect::<Result<_, _>>()?;
+                    }
+                    _ => return Err(error(&format!("unknown axis field {}", name))),
+                }
+            }
+        }
+    }
+    Ok((actions, axes))
+}
+
+/// The rest of a line being parsed
+struct Line<'a>(&'a str);
+
+impl Line<'_> {
+    fn skip_space(&mut self) {
+        self.0 = self.0.trim_start();
+    }
+
+    /// true at the end of the line or at a comment
+    fn at_end(&mut self) -> bool {
+        self.skip_space();
+        self.0.is_empty() || self.0.starts_with('#')
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip_space();
+        match self.0.strip_prefix(c) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn key(&mut self) -> Option<String> {
+        self.skip_space();
+        if self.0.starts_with('"') {
+            return self.string();
+        }
+        let end = self
+            .0
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == '-'))
+            .unwrap_or(self.0.len());
+        if end == 0 {
+            return None;
+        }
+        let (key, rest) = self.0.split_at(end);
+        self.0 = rest;
+        Some(key.to_string())
+    }
+
+    fn string(&mut self) -> Option<String> {
+        let mut chars = self.0.strip_prefix('"')?.char_indices();
+        let mut text = String::new();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.0 = &self.0[i + 2..];
+                    return Some(text);
+                }
+                '\\' => text.push(match chars.next()?.1 {
+                    'n' => '\n',
+                    't' => '\t',
+                    escaped => escaped,
+                }),
+                _ => text.push(c),
+            }
+        }
+        None
+    }
+
+    fn strings(&mut self) -> Option<Vec<String>> {
+        if !self.eat('[') {
+            return None;
+        }
+        let mut strings = Vec::new();
+        loop {
+            if self.eat(']') {
+                return Some(strings);
+            }
+            self.skip_space();
+            strings.push(self.string()?);
+            if !self.eat(',') {
+                return self.eat(']').then_some(strings);
+            }
+        }
+    }
+}
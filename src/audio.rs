@@ -2,17 +2,24 @@ use rodio::{Decoder, Source};
 use std::fs::File;
 use std::io::BufReader;
 use crate::core::MAYLIB;
+use crate::error::Error;
 
 pub fn play_sound(path: &str) {
+    try_play_sound(path).expect("Failed to play sound");
+}
+
+/// Play a sound file. If there is no audio device the sound is silently dropped
+pub fn try_play_sound(path: &str) -> Result<(), Error> {
     let mut get = MAYLIB.lock().expect("Should be able to lock");
     // Load the audio
-    let file = BufReader::new(File::open(path).expect("File not found"));
+    let file = BufReader::new(File::open(path)?);
     // Decode
-    let source = Decoder::new(file).expect("File not valid");
+    let source = Decoder::new(file).map_err(|e| Error::Decode(e.to_string()))?;
     // And play. It's that shrimple
     if let Some(a) = get.audio.as_mut() {
         a
             .play_raw(source.convert_samples())
-            .expect("TODO: panic message");
+            .map_err(|e| Error::Audio(e.to_string()))?;
     }
-}
\ No newline at end of file
+    Ok(())
+}
@@ -1,16 +1,38 @@
+use crate::error::Error;
 use crate::types;
-use crate::types::Maylib;
-use rand::{rng, Rng};
+use crate::gamepad::GamepadHotplug;
+use crate::{actions, events, gamepad, input, recording, touch};
+use crate::types::{FrameLimit, Maylib, WindowHandle, WindowInput, NO_WINDOW};
 use sdl2::event::{Event, WindowEvent};
 use sdl2::render::Canvas;
 use sdl2::video::FullscreenType;
 use sdl2::{pixels, video};
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
 use lazy_static::lazy_static;
 
+// These live in `random` now, and stay here so existing code keeps working
+pub use crate::random::{get_random_f64, get_random_i32, get_random_i64, try_get_random_f64};
+
+/// How much each new frame counts towards the smoothed fps
+const FPS_SMOOTHING: f64 = 0.1;
+
+/// How long before a frame deadline we stop sleeping and spin instead, since sleep can overshoot
+const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+/// Set by `init_maylib_headless` before maylib starts
+static HEADLESS: AtomicBool = AtomicBool::new(false);
+
 lazy_static! {
     pub static ref MAYLIB: Mutex<Maylib> = {
-        let mutex = Mutex::new(Maylib::init().expect("Can't init Maylib"));
+        let maylib = if HEADLESS.load(Ordering::SeqCst) {
+            Maylib::init_headless()
+        } else {
+            Maylib::init()
+        };
+        let mutex = Mutex::new(maylib.expect("Can't init Maylib"));
         mutex.clear_poison();
         mutex
     };
@@ -20,65 +42,394 @@ pub fn init_maylib() {
     lazy_static::initialize(&MAYLIB);
 }
 
-pub fn init_window(title: &str, width: u32, height: u32) -> Result<u32, String> {
+/// check if the app has been asked to quit, by the OS, Ctrl+C or SIGTERM.
+/// Every window is also marked to close when this happens
+pub fn app_should_quit() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.quit_requested
+}
+
+/// run `hook` when `close_maylib` is called
+pub fn on_shutdown(hook: impl FnOnce() + Send + 'static) {
     let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let winctx: video::Window = match get
-        .video
-        .window(title, width, height)
-        .position_centered()
-        .build()
-    {
-        Ok(w) => w,
-        Err(e) => return Err(e.to_string()),
-    };
-    let id = winctx.id();
-    let start_time = get.timer.ticks64() as f64 / 1000f64;
-    let canvas: Canvas<video::Window> = match winctx.clone().into_canvas().build() {
-        Ok(c) => c,
-        Err(e) => {
-            return Err(e.to_string());
-        }
+    get.shutdown_hooks.push(Box::new(hook));
+}
+
+/// shut maylib down: run shutdown hooks, then close every window and the audio stream.
+/// Call this once your main loop has finished
+pub fn close_maylib() {
+    let hooks = {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        std::mem::take(&mut get.shutdown_hooks)
     };
-    let window = types::Window {
-        texture: canvas.texture_creator(),
-        canvas,
-        ready: true,
-        should_close: false,
-        fullscreen: false,
-        hidden: false,
-        minimized: false,
-        maximized: false,
-        focused: false,
-        resized: false,
-        bordered: true,
-        window: winctx,
-        previous_time: 0.0,
-        current_time: start_time,
-        start_time,
+    // Hooks run without the lock so they can still use maylib
+    for hook in hooks {
+        hook();
+    }
+    let closed: Vec<WindowHandle> = {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let closed = get
+            .windows
+            .drain()
+            .map(|(id, window)| {
+                window.destroy();
+                WindowHandle::from_id(id)
+            })
+            .collect();
+        get.current_window = NO_WINDOW;
+        get.audio = None;
+        get._audio_stream = None;
+        closed
     };
-    get.windows.insert(id, window);
-    Ok(id)
+    notify_window_closed(&closed);
 }
 
-pub fn close_window() {
+/// start maylib without a display, GPU or audio device, for CI.
+/// Windows are drawn in software and frames run as fast as possible.
+/// Must be called before anything else in maylib, or it has no effect
+pub fn init_maylib_headless() {
+    HEADLESS.store(true, Ordering::SeqCst);
+    lazy_static::initialize(&MAYLIB);
+}
+
+/// check if maylib is running headless
+pub fn is_headless() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.headless
+}
+
+/// ask every window to close after `count` more frames.
+/// Useful with `init_maylib_headless` to run a fixed number of frames
+pub fn close_after_frames(count: u64) {
     let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let window = get.windows.remove(&current_window);
-    drop(window);
+    get.frames_left = Some(count);
+    if count == 0 {
+        for window in get.windows.values_mut() {
+            window.should_close = true;
+        }
+    }
 }
 
-pub fn window_should_close() -> bool {
+pub fn init_window(title: &str, width: u32, height: u32) -> Result<WindowHandle, Error> {
+    WindowBuilder::new(title, width, height).build()
+}
+
+/// Builds a window with more options than `init_window`
+///
+/// ```no_run
+/// use maylib::core::WindowBuilder;
+///
+/// let window = WindowBuilder::new("Editor", 1280, 720)
+///     .resizable()
+///     .vsync()
+///     .min_size(640, 360)
+///     .build()
+///     .expect("Window should open");
+/// ```
+#[derive(Clone, Debug)]
+pub struct WindowBuilder {
+    title: String,
+    width: u32,
+    height: u32,
+    position: Option<(i32, i32)>,
+    min_size: Option<(u32, u32)>,
+    max_size: Option<(u32, u32)>,
+    resizable: bool,
+    borderless: bool,
+    hidden: bool,
+    fullscreen: bool,
+    always_on_top: bool,
+    high_dpi: bool,
+    vsync: bool,
+    accelerated: bool,
+    software: bool,
+    target_texture: bool,
+}
+impl WindowBuilder {
+    /// Creates a builder for a centered, non-resizable window
+    pub fn new(title: &str, width: u32, height: u32) -> WindowBuilder {
+        WindowBuilder {
+            title: title.to_string(),
+            width,
+            height,
+            position: None,
+            min_size: None,
+            max_size: None,
+            resizable: false,
+            borderless: false,
+            hidden: false,
+            fullscreen: false,
+            always_on_top: false,
+            high_dpi: false,
+            vsync: false,
+            accelerated: false,
+            software: false,
+            target_texture: false,
+        }
+    }
+
+    /// Open the window at a fixed position instead of centered
+    pub fn position(mut self, x: i32, y: i32) -> WindowBuilder {
+        self.position = Some((x, y));
+        self
+    }
+
+    /// The smallest size the user can resize the window to
+    pub fn min_size(mut self, width: u32, height: u32) -> WindowBuilder {
+        self.min_size = Some((width, height));
+        self
+    }
+
+    /// The largest size the user can resize the window to
+    pub fn max_size(mut self, width: u32, height: u32) -> WindowBuilder {
+        self.max_size = Some((width, height));
+        self
+    }
+
+    pub fn resizable(mut self) -> WindowBuilder {
+        self.resizable = true;
+        self
+    }
+
+    pub fn borderless(mut self) -> WindowBuilder {
+        self.borderless = true;
+        self
+    }
+
+    /// Keep the window hidden until `show` is called
+    pub fn hidden(mut self) -> WindowBuilder {
+        self.hidden = true;
+        self
+    }
+
+    pub fn fullscreen(mut self) -> WindowBuilder {
+        self.fullscreen = true;
+        self
+    }
+
+    pub fn always_on_top(mut self) -> WindowBuilder {
+        self.always_on_top = true;
+        self
+    }
+
+    /// Use the full resolution of high-DPI displays
+    pub fn high_dpi(mut self) -> WindowBuilder {
+        self.high_dpi = true;
+        self
+    }
+
+    /// Sync presenting the canvas with the display's refresh rate
+    pub fn vsync(mut self) -> WindowBuilder {
+        self.vsync = true;
+        self
+    }
+
+    /// Ask for a hardware accelerated canvas
+    pub fn accelerated(mut self) -> WindowBuilder {
+        self.accelerated = true;
+        self
+    }
+
+    /// Ask for a software canvas
+    pub fn software(mut self) -> WindowBuilder {
+        self.software = true;
+        self
+    }
+
+    /// Allow the canvas to render into textures
+    pub fn target_texture(mut self) -> WindowBuilder {
+        self.target_texture = true;
+        self
+    }
+
+    /// Open the window
+    pub fn build(self) -> Result<WindowHandle, Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let mut builder = get.video.window(&self.title, self.width, self.height);
+        match self.position {
+            Some((x, y)) => builder.position(x, y),
+            None => builder.position_centered(),
+        };
+        if self.resizable {
+            builder.resizable();
+        }
+        if self.borderless {
+            builder.borderless();
+        }
+        if self.hidden {
+            builder.hidden();
+        }
+        if self.fullscreen {
+            builder.fullscreen();
+        }
+        if self.always_on_top {
+            builder.always_on_top();
+        }
+        if self.high_dpi {
+            builder.allow_highdpi();
+        }
+        let mut winctx: video::Window = builder
+            .build()
+            .map_err(|e| Error::Sdl(e.to_string()))?;
+        if let Some((width, height)) = self.min_size {
+            winctx
+                .set_minimum_size(width, height)
+                .map_err(|e| Error::Sdl(e.to_string()))?;
+        }
+        if let Some((width, height)) = self.max_size {
+            winctx
+                .set_maximum_size(width, height)
+                .map_err(|e| Error::Sdl(e.to_string()))?;
+        }
+        let id = winctx.id();
+        let start_time = get.time();
+        let mut canvas_builder = winctx.clone().into_canvas();
+        if self.vsync || get.frame_limit == FrameLimit::Vsync {
+            canvas_builder = canvas_builder.present_vsync();
+        }
+        if self.accelerated && !get.headless {
+            canvas_builder = canvas_builder.accelerated();
+        }
+        // There is no GPU to accelerate with when headless
+        if self.software || get.headless {
+            canvas_builder = canvas_builder.software();
+        }
+        if self.target_texture {
+            canvas_builder = canvas_builder.target_texture();
+        }
+        let canvas: Canvas<video::Window> = canvas_builder
+            .build()
+            .map_err(|e| Error::Sdl(e.to_string()))?;
+        let window = types::Window {
+            texture: canvas.texture_creator(),
+            canvas,
+            ready: true,
+            should_close: false,
+            fullscreen: self.fullscreen,
+            hidden: self.hidden,
+            minimized: false,
+            maximized: false,
+            focused: false,
+            resized: false,
+            moved: false,
+            hovered: false,
+            bordered: !self.borderless,
+            window: winctx,
+            previous_time: start_time,
+            current_time: start_time,
+            start_time,
+            frame_count: 0,
+            average_frame_time: 0.0,
+            close_requested_callback: None,
+            cursor: None,
+            vsync: self.vsync,
+            input: WindowInput::default(),
+        };
+        get.windows.insert(id, window);
+        Ok(WindowHandle::from_id(id))
+    }
+}
+
+/// get a handle to the current window
+pub fn get_current_window() -> WindowHandle {
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .should_close
+    WindowHandle::from_id(get.current_window)
+}
+
+/// get a handle to the current window, or `Error::NoWindow` if it isn't open
+pub fn try_get_current_window() -> Result<WindowHandle, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    if get.windows.contains_key(&get.current_window) {
+        Ok(WindowHandle::from_id(get.current_window))
+    } else {
+        Err(Error::NoWindow)
+    }
 }
 
+pub fn close_window() {
+    get_current_window().close();
+}
+
+pub fn try_close_window() -> Result<(), Error> {
+    try_get_current_window()?.try_close()
+}
+
+/// check if the current window should close. This is also true once there is no current window
+pub fn window_should_close() -> bool {
+    get_current_window().should_close()
+}
+
+pub fn try_window_should_close() -> Result<bool, Error> {
+    try_get_current_window()?.try_should_close()
+}
+
+/// ask the current window to close. Its `on_close_requested` callback can veto this
+pub fn request_window_close() {
+    get_current_window().request_close();
+}
+
+pub fn try_request_window_close() -> Result<(), Error> {
+    try_get_current_window()?.try_request_close()
+}
+
+/// decide whether the current window may close when the user or `request_close` asks.
+/// Return false from `callback` to keep the window open, e.g. to ask about unsaved changes
+pub fn on_close_requested(callback: impl FnMut(WindowHandle) -> bool + Send + 'static) {
+    get_current_window().on_close_requested(callback);
+}
+
+pub fn try_on_close_requested(callback: impl FnMut(WindowHandle) -> bool + Send + 'static) -> Result<(), Error> {
+    try_get_current_window()?.try_on_close_requested(callback)
+}
+
+/// run `callback` after any window closes
+pub fn on_window_closed(callback: impl FnMut(WindowHandle) + Send + 'static) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.window_closed_callbacks.push(Box::new(callback));
+}
+
+/// tell every `on_window_closed` callback that windows closed, without holding the lock
+fn notify_window_closed(windows: &[WindowHandle]) {
+    if windows.is_empty() {
+        return;
+    }
+    let mut callbacks = {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        std::mem::take(&mut get.window_closed_callbacks)
+    };
+    for window in windows {
+        for callback in callbacks.iter_mut() {
+            callback(*window);
+        }
+    }
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    // Keep any callbacks that were added while these ran
+    callbacks.append(&mut get.window_closed_callbacks);
+    get.window_closed_callbacks = callbacks;
+}
+
+/// set the target frame rate. A rate of 0 or less runs frames as fast as possible
 pub fn set_frame_rate(rate: i32) {
+    if rate > 0 {
+        set_frame_limit(FrameLimit::Fps(rate as u32));
+    } else {
+        set_frame_limit(FrameLimit::Unlimited);
+    }
+}
+
+/// set how `begin_drawing` paces frames
+pub fn set_frame_limit(limit: FrameLimit) {
     let mut get = MAYLIB.lock().expect("Should be able to lock");
-    get.frame_rate = rate;
-    get.frame_time = 1.0 / rate as f32;
+    get.frame_limit = limit;
+    // Windows that are already open were built with the old limit
+    for window in get.windows.values() {
+        let vsync = window.vsync || limit == FrameLimit::Vsync;
+        // SAFETY: the canvas owns a live renderer. SDL falls back to waiting itself when the
+        // renderer can't vsync, so this only fails for an invalid renderer
+        unsafe {
+            sdl2::sys::SDL_RenderSetVSync(window.canvas.raw(), vsync as i32);
+        }
+    }
 }
 
 pub fn all_windows_closed() -> bool {
@@ -92,226 +443,674 @@ pub fn all_windows_closed() -> bool {
 }
 
 pub fn is_window_ready() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .ready
+    get_current_window().is_ready()
+}
+
+pub fn try_is_window_ready() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_ready()
 }
 
 pub fn is_window_fullscreen() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .fullscreen
+    get_current_window().is_fullscreen()
+}
+
+pub fn try_is_window_fullscreen() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_fullscreen()
 }
 
 pub fn is_window_hidden() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .hidden
+    get_current_window().is_hidden()
+}
+
+pub fn try_is_window_hidden() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_hidden()
 }
 
 pub fn is_window_minimized() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .minimized
+    get_current_window().is_minimized()
+}
+
+pub fn try_is_window_minimized() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_minimized()
 }
 
 pub fn is_window_maximized() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .maximized
+    get_current_window().is_maximized()
+}
+
+pub fn try_is_window_maximized() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_maximized()
 }
 
 pub fn is_window_focused() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .focused
+    get_current_window().is_focused()
+}
+
+pub fn try_is_window_focused() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_focused()
 }
 
 pub fn is_window_resized() -> bool {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .resized
+    get_current_window().is_resized()
+}
+
+pub fn try_is_window_resized() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_resized()
+}
+
+/// check if the current window was moved this frame
+pub fn is_window_moved() -> bool {
+    get_current_window().is_moved()
+}
+
+pub fn try_is_window_moved() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_moved()
+}
+
+/// check if the mouse is over the current window
+pub fn is_window_hovered() -> bool {
+    get_current_window().is_hovered()
+}
+
+pub fn try_is_window_hovered() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_hovered()
 }
 
 pub fn toggle_fullscreen() {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let window = get.windows.get_mut(&current_window);
-    match window {
-        Some(w) => {
-            if w.fullscreen {
-                w.window
-                    .set_fullscreen(FullscreenType::Off)
-                    .expect("Window should exit fullscreen");
-            } else {
-                w.window
-                    .set_fullscreen(FullscreenType::True)
-                    .expect("Window should enter fullscreen");
-            }
-            w.fullscreen = !w.fullscreen;
-        }
-        None => { 
-            panic!("Window should be valid if loaded from switch_window")
-        }
-    }
+    get_current_window().toggle_fullscreen();
+}
+
+pub fn try_toggle_fullscreen() -> Result<(), Error> {
+    try_get_current_window()?.try_toggle_fullscreen()
 }
 
 pub fn toggle_borderless_windowed() {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    match get.windows.get_mut(&current_window) {
-        Some(w) => {
-            w.bordered = !w.bordered;
-        }
-        None => {
-            panic!("Window should be valid if loaded from switch_window");
-        }
-    }
-    let window = get
-        .windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window");
-    if window.bordered {
-        window.window.set_bordered(false);
-    } else {
-        window.window.set_bordered(true);
-    }
+    get_current_window().toggle_borderless_windowed();
+}
+
+pub fn try_toggle_borderless_windowed() -> Result<(), Error> {
+    try_get_current_window()?.try_toggle_borderless_windowed()
 }
 
 pub fn maximize_window() {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let window = get.windows.get_mut(&current_window).expect("Window should exist if loaded from switch_window");
-    window.maximized = true;
-    window.window.maximize();
+    get_current_window().maximize();
+}
+
+pub fn try_maximize_window() -> Result<(), Error> {
+    try_get_current_window()?.try_maximize()
 }
 
 pub fn minimize_window() {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let window = get.windows.get_mut(&current_window).expect("Window should exist if loaded from switch_window");
-    window.minimized = true;
-    window.window.minimize();
+    get_current_window().minimize();
+}
+
+pub fn try_minimize_window() -> Result<(), Error> {
+    try_get_current_window()?.try_minimize()
 }
 
 pub fn restore_window() {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let window = get.windows.get_mut(&current_window).expect("Window should exist if loaded from switch_window");
-    window.window.restore();
+    get_current_window().restore();
+}
+
+pub fn try_restore_window() -> Result<(), Error> {
+    try_get_current_window()?.try_restore()
+}
+
+pub fn show_window() {
+    get_current_window().show();
+}
+
+pub fn try_show_window() -> Result<(), Error> {
+    try_get_current_window()?.try_show()
+}
+
+pub fn hide_window() {
+    get_current_window().hide();
+}
+
+pub fn try_hide_window() -> Result<(), Error> {
+    try_get_current_window()?.try_hide()
 }
 
 pub fn set_window_title(title: &str) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .set_title(title)
-        .expect("Title should be valid. Does it contain invalid text?")
+    get_current_window().set_title(title);
+}
+
+pub fn try_set_window_title(title: &str) -> Result<(), Error> {
+    try_get_current_window()?.try_set_title(title)
 }
 
 pub fn set_window_position(x: i32, y: i32) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .set_position(
-            video::WindowPos::Positioned(x),
-            video::WindowPos::Positioned(y),
-        );
+    get_current_window().set_position(x, y);
+}
+
+pub fn try_set_window_position(x: i32, y: i32) -> Result<(), Error> {
+    try_get_current_window()?.try_set_position(x, y)
 }
 
 pub fn get_window_size() -> (u32, u32) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .size()
+    get_current_window().size()
+}
+
+pub fn try_get_window_size() -> Result<(u32, u32), Error> {
+    try_get_current_window()?.try_size()
 }
 
 pub fn set_window_size(width: u32, height: u32) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .set_size(width, height)
-        .expect("Size should be valid. Are any parameters 0?")
+    get_current_window().set_size(width, height);
+}
+
+pub fn try_set_window_size(width: u32, height: u32) -> Result<(), Error> {
+    try_get_current_window()?.try_set_size(width, height)
 }
 
 pub fn get_screen_width() -> i32 {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .display_mode()
-        .expect("There should be a valid display mode")
-        .w
+    get_current_window().screen_width()
+}
+
+pub fn try_get_screen_width() -> Result<i32, Error> {
+    try_get_current_window()?.try_screen_width()
 }
 
 pub fn get_screen_height() -> i32 {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .display_mode()
-        .expect("There should be a valid display mode")
-        .h
+    get_current_window().screen_height()
+}
+
+pub fn try_get_screen_height() -> Result<i32, Error> {
+    try_get_current_window()?.try_screen_height()
 }
 
 pub fn get_window_x() -> i32 {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .position()
-        .0
+    get_current_window().x()
+}
+
+pub fn try_get_window_x() -> Result<i32, Error> {
+    Ok(try_get_current_window()?.try_position()?.0)
 }
 
 pub fn get_window_y() -> i32 {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .position()
-        .1
+    get_current_window().y()
 }
 
+pub fn try_get_window_y() -> Result<i32, Error> {
+    Ok(try_get_current_window()?.try_position()?.1)
+}
+
+impl WindowHandle {
+    /// close this window right away, skipping `on_close_requested`.
+    /// If it was the current window, another open window becomes current
+    pub fn close(self) {
+        self.try_close().expect("Window handle should be valid")
+    }
+
+    pub fn try_close(self) -> Result<(), Error> {
+        {
+            let mut get = MAYLIB.lock().expect("Should be able to lock");
+            let window = get
+                .windows
+                .remove(&self.id)
+                .ok_or(Error::InvalidWindow(self.id))?;
+            window.destroy();
+            if get.current_window == self.id {
+                get.current_window = get.windows.keys().min().copied().unwrap_or(NO_WINDOW);
+            }
+        }
+        notify_window_closed(&[self]);
+        Ok(())
+    }
+
+    /// check if this window should close. This is also true once the window has been closed
+    pub fn should_close(self) -> bool {
+        self.try_should_close().unwrap_or(true)
+    }
+
+    pub fn try_should_close(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.should_close)
+    }
+
+    /// ask this window to close. Its `on_close_requested` callback can veto this
+    pub fn request_close(self) {
+        self.try_request_close().expect("Window handle should be valid")
+    }
+
+    pub fn try_request_close(self) -> Result<(), Error> {
+        let callback = {
+            let mut get = MAYLIB.lock().expect("Should be able to lock");
+            get.window_mut(self)?.close_requested_callback.take()
+        };
+        // The callback runs without the lock so it can use maylib
+        let allowed = match callback {
+            Some(mut callback) => {
+                let allowed = callback(self);
+                let mut get = MAYLIB.lock().expect("Should be able to lock");
+                if let Ok(window) = get.window_mut(self) {
+                    // Don't replace a callback that was set while this one ran
+                    window.close_requested_callback.get_or_insert(callback);
+                }
+                allowed
+            }
+            None => true,
+        };
+        if allowed {
+            let mut get = MAYLIB.lock().expect("Should be able to lock");
+            get.window_mut(self)?.should_close = true;
+        }
+        Ok(())
+    }
+
+    /// decide whether this window may close when the user or `request_close` asks.
+    /// Return false from `callback` to keep the window open
+    pub fn on_close_requested(self, callback: impl FnMut(WindowHandle) -> bool + Send + 'static) {
+        self.try_on_close_requested(callback)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_on_close_requested(self, callback: impl FnMut(WindowHandle) -> bool + Send + 'static) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?.close_requested_callback = Some(Box::new(callback));
+        Ok(())
+    }
+
+    pub fn is_ready(self) -> bool {
+        self.try_is_ready().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_ready(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.ready)
+    }
+
+    pub fn is_fullscreen(self) -> bool {
+        self.try_is_fullscreen().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_fullscreen(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.fullscreen)
+    }
+
+    pub fn is_hidden(self) -> bool {
+        self.try_is_hidden().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_hidden(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.hidden)
+    }
+
+    pub fn is_minimized(self) -> bool {
+        self.try_is_minimized().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_minimized(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.minimized)
+    }
+
+    pub fn is_maximized(self) -> bool {
+        self.try_is_maximized().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_maximized(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.maximized)
+    }
+
+    pub fn is_focused(self) -> bool {
+        self.try_is_focused().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_focused(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.focused)
+    }
+
+    pub fn is_resized(self) -> bool {
+        self.try_is_resized().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_resized(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.resized)
+    }
+
+    /// check if this window was moved this frame
+    pub fn is_moved(self) -> bool {
+        self.try_is_moved().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_moved(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.moved)
+    }
+
+    /// check if the mouse is over this window
+    pub fn is_hovered(self) -> bool {
+        self.try_is_hovered().expect("Window handle should be valid")
+    }
+
+    pub fn try_is_hovered(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.hovered)
+    }
+
+    pub fn toggle_fullscreen(self) {
+        self.try_toggle_fullscreen()
+            .expect("Window should toggle fullscreen")
+    }
+
+    pub fn try_toggle_fullscreen(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let w = get.window_mut(self)?;
+        if w.fullscreen {
+            w.window
+                .set_fullscreen(FullscreenType::Off)
+                .map_err(Error::Sdl)?;
+        } else {
+            w.window
+                .set_fullscreen(FullscreenType::True)
+                .map_err(Error::Sdl)?;
+        }
+        w.fullscreen = !w.fullscreen;
+        Ok(())
+    }
+
+    pub fn toggle_borderless_windowed(self) {
+        self.try_toggle_borderless_windowed()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_toggle_borderless_windowed(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        window.bordered = !window.bordered;
+        window.window.set_bordered(window.bordered);
+        Ok(())
+    }
+
+    pub fn maximize(self) {
+        self.try_maximize().expect("Window handle should be valid")
+    }
+
+    pub fn try_maximize(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        window.maximized = true;
+        window.minimized = false;
+        window.window.maximize();
+        Ok(())
+    }
+
+    pub fn minimize(self) {
+        self.try_minimize().expect("Window handle should be valid")
+    }
+
+    pub fn try_minimize(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        window.minimized = true;
+        window.maximized = false;
+        window.window.minimize();
+        Ok(())
+    }
+
+    pub fn restore(self) {
+        self.try_restore().expect("Window handle should be valid")
+    }
+
+    pub fn try_restore(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        window.minimized = false;
+        window.maximized = false;
+        window.window.restore();
+        Ok(())
+    }
+
+    pub fn show(self) {
+        self.try_show().expect("Window handle should be valid")
+    }
+
+    pub fn try_show(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        window.hidden = false;
+        window.window.show();
+        Ok(())
+    }
+
+    pub fn hide(self) {
+        self.try_hide().expect("Window handle should be valid")
+    }
+
+    pub fn try_hide(self) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        window.hidden = true;
+        window.window.hide();
+        Ok(())
+    }
+
+    pub fn set_title(self, title: &str) {
+        self.try_set_title(title)
+            .expect("Title should be valid. Does it contain invalid text?")
+    }
+
+    pub fn try_set_title(self, title: &str) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .window
+            .set_title(title)
+            .map_err(|e| Error::Sdl(e.to_string()))
+    }
+
+    pub fn set_position(self, x: i32, y: i32) {
+        self.try_set_position(x, y)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_set_position(self, x: i32, y: i32) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?.window.set_position(
+            video::WindowPos::Positioned(x),
+            video::WindowPos::Positioned(y),
+        );
+        Ok(())
+    }
+
+    /// get the size of this window
+    pub fn size(self) -> (u32, u32) {
+        self.try_size().expect("Window handle should be valid")
+    }
+
+    pub fn try_size(self) -> Result<(u32, u32), Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.window.size())
+    }
+
+    pub fn set_size(self, width: u32, height: u32) {
+        self.try_set_size(width, height)
+            .expect("Size should be valid. Are any parameters 0?")
+    }
+
+    pub fn try_set_size(self, width: u32, height: u32) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .window
+            .set_size(width, height)
+            .map_err(|e| Error::Sdl(e.to_string()))
+    }
+
+    /// get the width of the display this window is on
+    pub fn screen_width(self) -> i32 {
+        self.try_screen_width()
+            .expect("There should be a valid display mode")
+    }
+
+    pub fn try_screen_width(self) -> Result<i32, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.window.display_mode().map_err(Error::Sdl)?.w)
+    }
+
+    /// get the height of the display this window is on
+    pub fn screen_height(self) -> i32 {
+        self.try_screen_height()
+            .expect("There should be a valid display mode")
+    }
+
+    pub fn try_screen_height(self) -> Result<i32, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.window.display_mode().map_err(Error::Sdl)?.h)
+    }
+
+    pub fn x(self) -> i32 {
+        self.position().0
+    }
+
+    pub fn y(self) -> i32 {
+        self.position().1
+    }
+
+    /// get the position of this window
+    pub fn position(self) -> (i32, i32) {
+        self.try_position().expect("Window handle should be valid")
+    }
+
+    pub fn try_position(self) -> Result<(i32, i32), Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.window.position())
+    }
+
+    /// clear the background of this window
+    pub fn clear_background(self, color: types::Color) {
+        self.try_clear_background(color)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_clear_background(self, color: types::Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let canvas = &mut get.window_mut(self)?.canvas;
+        canvas.set_draw_color(pixels::Color::from(color));
+        canvas.clear();
+        Ok(())
+    }
+
+    /// get the time this window started its current frame
+    pub fn time(self) -> f64 {
+        self.try_time().expect("Window handle should be valid")
+    }
+
+    pub fn try_time(self) -> Result<f64, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.current_time)
+    }
+
+    /// get the time in seconds this window's last frame took
+    pub fn frame_time(self) -> f64 {
+        self.try_frame_time().expect("Window handle should be valid")
+    }
+
+    pub fn try_frame_time(self) -> Result<f64, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window(self)?;
+        Ok(window.current_time - window.previous_time)
+    }
+
+    /// get this window's frames per second, smoothed over recent frames
+    pub fn fps(self) -> f64 {
+        self.try_fps().expect("Window handle should be valid")
+    }
+
+    pub fn try_fps(self) -> Result<f64, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window(self)?;
+        if window.average_frame_time > 0.0 {
+            Ok(1.0 / window.average_frame_time)
+        } else {
+            Ok(0.0)
+        }
+    }
+
+    /// get the number of frames this window has drawn
+    pub fn frame_count(self) -> u64 {
+        self.try_frame_count().expect("Window handle should be valid")
+    }
+
+    pub fn try_frame_count(self) -> Result<u64, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.frame_count)
+    }
+
+    /// get the time in seconds since this window was opened
+    pub fn uptime(self) -> f64 {
+        self.try_uptime().expect("Window handle should be valid")
+    }
+
+    pub fn try_uptime(self) -> Result<f64, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window(self)?;
+        Ok(window.current_time - window.start_time)
+    }
+}
+
+/// get the text on the clipboard, or `None` if it is empty, holds something other than text, or can't be read
 pub fn get_clipboard_text() -> Option<String> {
+    try_get_clipboard_text().ok().flatten()
+}
+
+pub fn try_get_clipboard_text() -> Result<Option<String>, Error> {
     let get = MAYLIB.lock().expect("Should be able to lock");
-    Some(get.clipboard.clipboard_text().expect("TEXT SHOULD EXIST"))
+    // SDL gives an empty string when there is no text, so check first
+    if !get.clipboard.has_clipboard_text() {
+        return Ok(None);
+    }
+    get.clipboard.clipboard_text().map(Some).map_err(Error::Sdl)
+}
+
+/// check if the clipboard has text on it
+pub fn has_clipboard_text() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.clipboard.has_clipboard_text()
 }
 
 pub fn set_clipboard_text(text: &str) {
+    try_set_clipboard_text(text).expect("Can't set clipboard text");
+}
+
+pub fn try_set_clipboard_text(text: &str) -> Result<(), Error> {
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.clipboard
-        .set_clipboard_text(text)
-        .expect("Clipboard should be valid");
+    get.clipboard.set_clipboard_text(text).map_err(Error::Sdl)
+}
+
+/// get the text selected in any app, which middle click pastes on Linux.
+/// Only X11 and Wayland have a primary selection, so this is always `None` elsewhere
+pub fn get_primary_selection_text() -> Option<String> {
+    try_get_primary_selection_text().ok().flatten()
+}
+
+pub fn try_get_primary_selection_text() -> Result<Option<String>, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    if !get.clipboard.has_primary_selection_text() {
+        return Ok(None);
+    }
+    get.clipboard.primary_selection_text().map(Some).map_err(Error::Sdl)
+}
+
+/// check if any app has text selected. Always false outside X11 and Wayland
+pub fn has_primary_selection_text() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.clipboard.has_primary_selection_text()
+}
+
+/// make this the selected text other apps paste with middle click. Only works on X11 and Wayland
+pub fn set_primary_selection_text(text: &str) {
+    try_set_primary_selection_text(text).expect("Can't set primary selection text");
+}
+
+pub fn try_set_primary_selection_text(text: &str) -> Result<(), Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.clipboard.set_primary_selection_text(text).map_err(Error::Sdl)
 }
 
 pub fn show_cursor() {
@@ -328,77 +1127,190 @@ pub fn hide_cursor() {
 /// check if the cursor is hidden
 pub fn cursor_hidden() -> bool {
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.mouse.is_cursor_showing()
+    !get.mouse.is_cursor_showing()
 }
 
 /// clear the background of the current window
 pub fn clear_background(color: types::Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .set_draw_color(pixels::Color::from(color));
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .clear();
+    get_current_window().clear_background(color);
+}
+
+pub fn try_clear_background(color: types::Color) -> Result<(), Error> {
+    try_get_current_window()?.try_clear_background(color)
 }
 
 pub fn begin_drawing() {
-    let frame_time;
+    pace_frame();
+    let mut close_requests = Vec::new();
+    let mut gamepad_changes = Vec::new();
     {
         let mut get = MAYLIB.lock().expect("Should be able to lock");
-        let time = get.timer.ticks64() as f64 / 1000f64;
-        let keys: Vec<u32> = get.windows.keys().copied().collect();
-        let windows= &mut get.windows;
-        for key in keys {
-            let current_time = windows.get_mut(&key).expect("Window should exist if in keys").current_time;
-            windows.get_mut(&key).expect("Window should exist if in keys").previous_time = current_time;
-            windows.get_mut(&key).expect("Window should exist if in keys").current_time = time;
+        let playback_events = recording::next_playback_frame(&mut get);
+        let time = get.time();
+        for window in get.windows.values_mut() {
+            window.previous_time = window.current_time;
+            window.current_time = time;
+            window.frame_count += 1;
+            // Smooth the fps so it doesn't jump around every frame
+            let frame_time = window.current_time - window.previous_time;
+            if window.frame_count == 1 {
+                window.average_frame_time = frame_time;
+            } else {
+                window.average_frame_time = window.average_frame_time * (1.0 - FPS_SMOOTHING)
+                    + frame_time * FPS_SMOOTHING;
+            }
+            // Resized and moved only hold for the frame the event arrived in
+            window.resized = false;
+            window.moved = false;
+            window.input.begin_frame();
+            let touching = !window.input.touch_points.is_empty();
+            window.input.gestures.begin_frame(touching, time);
+        }
+        gamepad::begin_frame(&mut get);
+        let mut events: Vec<_> = get.event_pump.poll_iter().collect();
+        if let Some(recorded) = playback_events {
+            // Live input is ignored while playing back, but the app can still be quit
+            events.retain(|event| matches!(event, Event::Quit { .. } | Event::AppTerminating { .. }));
+            events.extend(recorded);
+        }
+        get.events.clear();
+        for hotplug in std::mem::take(&mut get.gamepad_hotplugs) {
+            get.events.push_back(events::hotplug_event(&hotplug));
+            gamepad_changes.push(hotplug);
         }
-        let events: Vec<_> = get.event_pump.poll_iter().collect();
-        let windows = &mut get.windows;
         for event in events {
-            match event {
-                Event::Window {
-                    timestamp: _,
-                    window_id,
-                    win_event,
-                } => match win_event {
-                    WindowEvent::Close => {
-                        windows
-                            .get_mut(&window_id)
-                            .expect("Window should exist if SDL passes it to us")
-                            .should_close = true;
-                    }
-                    WindowEvent::FocusLost => {
-                        windows
-                            .get_mut(&window_id)
-                            .expect("Window should exist if SDL passes it to us")
-                            .focused = false;
-                    }
-                    WindowEvent::FocusGained => {
-                        windows
-                            .get_mut(&window_id)
-                            .expect("Window should exist if SDL passes it to us")
-                            .focused = true;
-                    }
-                    _ => {}
-                },
-                Event::AppTerminating { timestamp: _ } => {
-                    // Just die peacefully
-                    #[allow(clippy::empty_loop)]
-                    loop {}
+            let Some(event) = recording::replay_event(&mut get, event) else {
+                continue;
+            };
+            if let Some(converted) = handle_event(&mut get, event, &mut close_requests, &mut gamepad_changes) {
+                get.events.push_back(converted);
+            }
+        }
+        // Events taken by `wait_event_timeout` since the last frame are recorded with this one
+        recording::record_frame(&mut get, time);
+    }
+    finish_events(close_requests, gamepad_changes);
+}
+
+/// update maylib's state from an SDL event and turn it into a maylib event.
+/// Close requests and gamepad changes are collected to handle once the lock is released
+pub(crate) fn handle_event(
+    get: &mut Maylib,
+    event: Event,
+    close_requests: &mut Vec<WindowHandle>,
+    gamepad_changes: &mut Vec<GamepadHotplug>,
+) -> Option<types::Event> {
+    input::record_event(get, &event);
+    actions::record_event(get, &event);
+    let touch = touch::record_event(get, &event);
+    let hotplug = gamepad::record_event(get, &event);
+    recording::record_event(get, &event, hotplug.as_ref());
+    let converted = events::convert(get, &event, hotplug.as_ref(), touch);
+    gamepad_changes.extend(hotplug);
+    let windows = &mut get.windows;
+    match event {
+        Event::Window {
+            timestamp: _,
+            window_id,
+            win_event,
+        } => {
+            // SDL can still send events for a window we just closed
+            let Some(window) = windows.get_mut(&window_id) else {
+                return converted;
+            };
+            match win_event {
+                WindowEvent::Close => {
+                    close_requests.push(WindowHandle::from_id(window_id));
+                }
+                WindowEvent::FocusLost => {
+                    window.focused = false;
+                }
+                WindowEvent::FocusGained => {
+                    window.focused = true;
+                }
+                WindowEvent::Resized(..) | WindowEvent::SizeChanged(..) => {
+                    window.resized = true;
+                }
+                WindowEvent::Moved(..) => {
+                    window.moved = true;
+                }
+                WindowEvent::Minimized => {
+                    window.minimized = true;
+                    window.maximized = false;
+                }
+                WindowEvent::Maximized => {
+                    window.maximized = true;
+                    window.minimized = false;
+                }
+                WindowEvent::Restored => {
+                    window.minimized = false;
+                    window.maximized = false;
+                }
+                WindowEvent::Shown => {
+                    window.hidden = false;
+                }
+                WindowEvent::Hidden => {
+                    window.hidden = true;
+                }
+                WindowEvent::Enter => {
+                    window.hovered = true;
+                }
+                WindowEvent::Leave => {
+                    window.hovered = false;
                 }
                 _ => {}
             }
         }
-        frame_time = get.frame_time;
+        // Quit comes from the OS or Ctrl+C/SIGTERM and can't be vetoed
+        Event::Quit { timestamp: _ } | Event::AppTerminating { timestamp: _ } => {
+            get.quit_requested = true;
+            for window in get.windows.values_mut() {
+                window.should_close = true;
+            }
+        }
+        _ => {}
+    }
+    converted
+}
+
+/// run callbacks for what `handle_event` collected, without the lock held
+pub(crate) fn finish_events(close_requests: Vec<WindowHandle>, gamepad_changes: Vec<GamepadHotplug>) {
+    gamepad::notify_hotplug(&gamepad_changes);
+    for window in close_requests {
+        // The window may have been closed by an earlier callback
+        let _ = window.try_request_close();
+    }
+}
+
+/// wait until the current frame has taken as long as the frame limit asks for
+fn pace_frame() {
+    let remaining = {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        match (get.frame_limit, get.frame_start) {
+            // Playback runs on the recorded clock, so there's nothing to wait for
+            (FrameLimit::Fps(fps), Some(start)) if fps > 0 && get.playback.is_none() => {
+                Duration::from_secs_f64(1.0 / fps as f64).checked_sub(start.elapsed())
+            }
+            _ => None,
+        }
+    };
+    // Don't hold the lock while waiting so other threads can still use maylib
+    if let Some(remaining) = remaining {
+        sleep_precise(remaining);
+    }
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.frame_start = Some(Instant::now());
+}
+
+/// sleep for most of the duration, then spin for the rest
+fn sleep_precise(duration: Duration) {
+    let deadline = Instant::now() + duration;
+    if let Some(sleep) = duration.checked_sub(SPIN_MARGIN) {
+        thread::sleep(sleep);
+    }
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
     }
-    wait(frame_time as f64);
 }
 
 /// end drawing
@@ -407,89 +1319,119 @@ pub fn end_drawing() {
     for window in get.windows.values_mut() {
         window.canvas.present();
     }
+    if let Some(frames_left) = get.frames_left {
+        let frames_left = frames_left.saturating_sub(1);
+        if frames_left == 0 {
+            // The countdown is over, so windows opened after this stay open
+            get.frames_left = None;
+            for window in get.windows.values_mut() {
+                window.should_close = true;
+            }
+        } else {
+            get.frames_left = Some(frames_left);
+        }
+    }
 }
 
-/// switch window
-pub fn switch_window(id: u32) {
+/// switch window. Takes a `WindowHandle`, or a window id as older code passed
+pub fn switch_window(window: impl Into<WindowHandle>) {
     let mut get = MAYLIB.lock().expect("Should be able to lock");
-    get.current_window = id;
+    get.current_window = window.into().id;
 }
 
 /// get the time since opening sdl
 pub fn get_time() -> f64 {
-    let get = MAYLIB.lock().expect("Should be able to lock");
-    get.windows
-        .get(&get.current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .current_time
+    get_current_window().time()
 }
 
-/// wait an amount of time
-pub fn wait(time: f64) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let start: f64 = get.timer.ticks64() as f64 / 1000f64;
-    let mut current: f64 = get.timer.ticks64() as f64 / 1000f64;
-
-    while current < start + time {
-        get.windows
-            .get_mut(&current_window)
-            .expect("Window should be valid if loaded from switch_window")
-            .previous_time = get
-            .windows
-            .get(&current_window)
-            .expect("Window should be valid if loaded from switch_window")
-            .current_time;
-        get.windows
-            .get_mut(&current_window)
-            .expect("Window should be valid if loaded from switch_window")
-            .current_time = get.timer.ticks64() as f64 / 1000f64;
-        current = get.timer.ticks64() as f64 / 1000f64;
-    }
+pub fn try_get_time() -> Result<f64, Error> {
+    try_get_current_window()?.try_time()
+}
+
+/// get the time in seconds the current window's last frame took
+pub fn get_frame_time() -> f64 {
+    get_current_window().frame_time()
 }
 
-pub fn get_random_i32(min: i32, max: i32) -> i32 {
-    let mut rand = rand::rng();
-    rand.random_range(min..max)
+pub fn try_get_frame_time() -> Result<f64, Error> {
+    try_get_current_window()?.try_frame_time()
 }
 
-pub fn get_random_i64(min: i64, max: i64) -> i64 {
-    let mut rand = rng();
-    rand.random_range(min..max)
+/// get the current window's frames per second, smoothed over recent frames
+pub fn get_fps() -> f64 {
+    get_current_window().fps()
 }
 
-pub fn get_random_f64(min: f64, max: f64) -> f64 {
-    let mut rand = rng();
-    rand.random_range(min..max)
+pub fn try_get_fps() -> Result<f64, Error> {
+    try_get_current_window()?.try_fps()
+}
+
+/// get the number of frames the current window has drawn
+pub fn get_frame_count() -> u64 {
+    get_current_window().frame_count()
+}
+
+pub fn try_get_frame_count() -> Result<u64, Error> {
+    try_get_current_window()?.try_frame_count()
+}
+
+/// get the time in seconds since the current window was opened
+pub fn get_window_uptime() -> f64 {
+    get_current_window().uptime()
+}
+
+pub fn try_get_window_uptime() -> Result<f64, Error> {
+    try_get_current_window()?.try_uptime()
+}
+
+/// wait an amount of time in seconds
+pub fn wait(time: f64) {
+    if time > 0.0 {
+        sleep_precise(Duration::from_secs_f64(time));
+    }
 }
 
 pub fn open_url(url: &str) {
     open::that(url).expect("Should be able to open URL");
 }
 
-//TODO: Move to custom Scancode struct
-pub fn key_pressed(key: types::Keycode) -> bool {
+/// check if a key is held down in any window. Use `input::is_key_down` for the current window
+pub fn key_pressed(key: impl Into<types::Scancode>) -> bool {
+    let scancode: types::Scancode = key.into();
+    // Read what `begin_drawing` recorded rather than SDL, so recordings play back here too
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.event_pump
-        .keyboard_state()
-        .is_scancode_pressed(key.into())
+    get.windows
+        .values()
+        .any(|window| window.input.keys_down.contains(&scancode))
 }
 
+/// check if a mouse button is held down in any window. Use `input::is_mouse_button_down` for the current window
 pub fn mouse_button_pressed(button: types::MouseButton) -> bool {
+    let button = sdl2::mouse::MouseButton::from(button);
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.event_pump
-        .mouse_state()
-        .is_mouse_button_pressed(sdl2::mouse::MouseButton::from(button))
+    get.windows
+        .values()
+        .any(|window| window.input.buttons_down.contains(&button))
+}
+
+/// get the mouse position in whichever window the mouse is over, or else the current window
+fn mouse_position(get: &Maylib) -> (i32, i32) {
+    get.windows
+        .values()
+        .find(|window| window.hovered)
+        .or_else(|| get.windows.get(&get.current_window))
+        .map(|window| (window.input.mouse_x, window.input.mouse_y))
+        .unwrap_or_default()
 }
 
-/// get the mouse x
+/// get the mouse x, relative to whichever window the mouse is over
 pub fn get_mouse_x() -> i32 {
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.event_pump.mouse_state().x()
+    mouse_position(&get).0
 }
 
-/// get the mouse y
+/// get the mouse y, relative to whichever window the mouse is over
 pub fn get_mouse_y() -> i32 {
     let get = MAYLIB.lock().expect("Should be able to lock");
-    get.event_pump.mouse_state().y()
+    mouse_position(&get).1
 }
@@ -0,0 +1,59 @@
+use std::fmt;
+use std::io;
+
+/// Everything that can go wrong inside maylib
+#[derive(Debug)]
+pub enum Error {
+    /// There is no current window. Call `init_window` and `switch_window` first
+    NoWindow,
+    /// The window with this id has been closed or never existed
+    InvalidWindow(u32),
+    /// No gamepad is connected in this slot
+    InvalidGamepad(usize),
+    /// No action or axis has been bound with this name
+    InvalidAction(String),
+    /// A random number range has a bound that is NaN or infinite, or is too wide to pick from
+    InvalidRange(f64, f64),
+    /// A file could not be read or written
+    Io(io::Error),
+    /// An image or sound could not be decoded
+    Decode(String),
+    /// SDL reported an error
+    Sdl(String),
+    /// The audio device failed to play a sound
+    Audio(String),
+    /// A font could not be loaded or rendered
+    Font(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoWindow => write!(f, "no current window"),
+            Error::InvalidWindow(id) => write!(f, "window {} does not exist", id),
+            Error::InvalidGamepad(slot) => write!(f, "no gamepad is connected in slot {}", slot),
+            Error::InvalidAction(name) => write!(f, "no action or axis is named {}", name),
+            Error::InvalidRange(min, max) => write!(f, "can't pick a random number from {} to {}", min, max),
+            Error::Io(e) => write!(f, "io error: {}", e),
+            Error::Decode(e) => write!(f, "decode error: {}", e),
+            Error::Sdl(e) => write!(f, "sdl error: {}", e),
+            Error::Audio(e) => write!(f, "audio error: {}", e),
+            Error::Font(e) => write!(f, "font error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Io(value)
+    }
+}
@@ -0,0 +1,185 @@
+use crate::core::{self, MAYLIB};
+use crate::gamepad::{self, GamepadHotplug};
+use crate::types::{Event, Keycode, Maylib, MouseButton, Scancode, TouchPoint, WindowEvent, WindowHandle};
+use sdl2::event::{Event as SdlEvent, WindowEvent as SdlWindowEvent};
+use sdl2::mouse::MouseWheelDirection;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// The longest `wait_event_timeout` holds maylib while it waits
+const WAIT_SLICE: Duration = Duration::from_millis(10);
+
+/// take everything that happened since `begin_drawing`, oldest first.
+/// Events that aren't taken are dropped at the next `begin_drawing`
+pub fn poll_events() -> impl Iterator<Item = Event> {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    std::mem::take(&mut get.events).into_iter()
+}
+
+/// sleep until something happens or `timeout` seconds pass, and take what happened.
+/// Events already waiting from this frame come first. Like `begin_drawing`, only call this from the main thread.
+/// Keys and buttons taken here are held down as usual, but their presses are over by the next `begin_drawing`,
+/// so handle them from the event
+pub fn wait_event_timeout(timeout: f64) -> Option<Event> {
+    let deadline = Instant::now() + Duration::from_secs_f64(timeout.max(0.0));
+    loop {
+        let mut close_requests = Vec::new();
+        let mut gamepad_changes = Vec::new();
+        let (event, done) = {
+            let mut get = MAYLIB.lock().expect("Should be able to lock");
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if let Some(event) = get.events.pop_front() {
+                (Some(event), true)
+            } else if get.playback.is_some() || remaining.is_zero() {
+                // A recording replaces live input, so there is nothing to wait for
+                (None, true)
+            } else {
+                // Wait a slice at a time, so other threads can use maylib in between
+                let slice = remaining.min(WAIT_SLICE).as_millis().max(1) as u32;
+                let event = get.event_pump.wait_event_timeout(slice).and_then(|sdl_event| {
+                    core::handle_event(&mut get, sdl_event, &mut close_requests, &mut gamepad_changes)
+                });
+                (event, false)
+            }
+        };
+        core::finish_events(close_requests, gamepad_changes);
+        // Events maylib doesn't pass on still count, but don't end the wait
+        if done || event.is_some() {
+            return event;
+        }
+    }
+}
+
+/// turn a gamepad plugging in or out into a maylib event
+pub(crate) fn hotplug_event(hotplug: &GamepadHotplug) -> Event {
+    match *hotplug {
+        GamepadHotplug::Connected(gamepad) => Event::GamepadConnected { gamepad },
+        GamepadHotplug::Disconnected(gamepad) => Event::GamepadDisconnected { gamepad },
+    }
+}
+
+/// turn an SDL event into a maylib one, called by `begin_drawing` after the event has been recorded
+pub(crate) fn convert(
+    get: &Maylib,
+    event: &SdlEvent,
+    hotplug: Option<&GamepadHotplug>,
+    touch: Option<(u32, TouchPoint)>,
+) -> Option<Event> {
+    if let Some(hotplug) = hotplug {
+        return Some(hotplug_event(hotplug));
+    }
+    let window = |window_id: u32| {
+        // SDL can still send events for a window that was just closed
+        get.windows
+            .contains_key(&window_id)
+            .then(|| WindowHandle::from_id(window_id))
+    };
+    Some(match *event {
+        SdlEvent::Window { window_id, win_event, .. } => {
+            let event = match win_event {
+                SdlWindowEvent::Shown => WindowEvent::Shown,
+                SdlWindowEvent::Hidden => WindowEvent::Hidden,
+                SdlWindowEvent::Moved(x, y) => WindowEvent::Moved(x, y),
+                SdlWindowEvent::Resized(width, height) => {
+                    WindowEvent::Resized(width.max(0) as u32, height.max(0) as u32)
+                }
+                SdlWindowEvent::Minimized => WindowEvent::Minimized,
+                SdlWindowEvent::Maximized => WindowEvent::Maximized,
+                SdlWindowEvent::Restored => WindowEvent::Restored,
+                SdlWindowEvent::Enter => WindowEvent::MouseEntered,
+                SdlWindowEvent::Leave => WindowEvent::MouseLeft,
+                SdlWindowEvent::FocusGained => WindowEvent::FocusGained,
+                SdlWindowEvent::FocusLost => WindowEvent::FocusLost,
+                SdlWindowEvent::Close => WindowEvent::CloseRequested,
+                // SizeChanged always comes with Resized when the user resizes
+                _ => return None,
+            };
+            Event::Window { window: window(window_id)?, event }
+        }
+        SdlEvent::KeyDown { window_id, scancode: Some(scancode), keycode, repeat, .. } => Event::KeyDown {
+            window: window(window_id)?,
+            key: Scancode::from(scancode),
+            keycode: keycode.map(Keycode::from).unwrap_or(Keycode::Unknown),
+            repeat,
+        },
+        SdlEvent::KeyUp { window_id, scancode: Some(scancode), keycode, .. } => Event::KeyUp {
+            window: window(window_id)?,
+            key: Scancode::from(scancode),
+            keycode: keycode.map(Keycode::from).unwrap_or(Keycode::Unknown),
+        },
+        SdlEvent::TextInput { window_id, ref text, .. } => {
+            let window = window(window_id)?;
+            // Text input is tracked per window, so only pass it on where it is turned on
+            if !get.windows.get(&window_id)?.input.text_input {
+                return None;
+            }
+            Event::TextInput { window, text: text.clone() }
+        }
+        SdlEvent::MouseMotion { window_id, x, y, xrel, yrel, .. } => Event::MouseMotion {
+            window: window(window_id)?,
+            x,
+            y,
+            dx: xrel,
+            dy: yrel,
+        },
+        SdlEvent::MouseButtonDown { window_id, mouse_btn, clicks, x, y, .. } => Event::MouseButtonDown {
+            window: window(window_id)?,
+            button: MouseButton::from_sdl(mouse_btn)?,
+            x,
+            y,
+            clicks,
+        },
+        SdlEvent::MouseButtonUp { window_id, mouse_btn, x, y, .. } => Event::MouseButtonUp {
+            window: window(window_id)?,
+            button: MouseButton::from_sdl(mouse_btn)?,
+            x,
+            y,
+        },
+        SdlEvent::MouseWheel { window_id, direction, precise_x, precise_y, .. } => {
+            // Same as `get_mouse_wheel_move`, up is always positive
+            let sign = match direction {
+                MouseWheelDirection::Flipped => -1.0,
+                _ => 1.0,
+            };
+            Event::MouseWheel {
+                window: window(window_id)?,
+                x: precise_x * sign,
+                y: precise_y * sign,
+            }
+        }
+        SdlEvent::DropFile { window_id, ref filename, .. } => Event::FileDropped {
+            window: window(window_id)?,
+            path: PathBuf::from(filename),
+        },
+        SdlEvent::DropText { window_id, ref filename, .. } => Event::TextDropped {
+            window: window(window_id)?,
+            text: filename.clone(),
+        },
+        SdlEvent::ControllerButtonDown { which, button, .. } => Event::GamepadButtonDown {
+            gamepad: gamepad::find_slot(get, which)?,
+            button: button.into(),
+        },
+        SdlEvent::ControllerButtonUp { which, button, .. } => Event::GamepadButtonUp {
+            gamepad: gamepad::find_slot(get, which)?,
+            button: button.into(),
+        },
+        SdlEvent::ControllerAxisMotion { which, axis, value, .. } => Event::GamepadAxisMotion {
+            gamepad: gamepad::find_slot(get, which)?,
+            axis: axis.into(),
+            value: (value as f32 / i16::MAX as f32).max(-1.0),
+        },
+        SdlEvent::FingerDown { .. } | SdlEvent::FingerUp { .. } | SdlEvent::FingerMotion { .. } => {
+            // Lifted fingers are already gone from the window, so this comes from `touch::record_event`
+            let (window_id, point) = touch?;
+            let window = WindowHandle::from_id(window_id);
+            match *event {
+                SdlEvent::FingerDown { .. } => Event::TouchDown { window, point },
+                SdlEvent::FingerUp { .. } => Event::TouchUp { window, point },
+                _ => Event::TouchMotion { window, point },
+            }
+        }
+        SdlEvent::ClipboardUpdate { .. } => Event::ClipboardUpdated,
+        SdlEvent::Quit { .. } | SdlEvent::AppTerminating { .. } => Event::Quit,
+        _ => return None,
+    })
+}
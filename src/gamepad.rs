@@ -0,0 +1,366 @@
+use crate::core::MAYLIB;
+use crate::error::Error;
+use crate::recording;
+use crate::types::{Gamepad, GamepadAxis, GamepadButton, Maylib};
+use sdl2::event::Event;
+use sdl2::sys::{SDL_GameControllerButton, SDL_JoystickType};
+use std::collections::{HashMap, HashSet};
+
+/// How many axes and buttons a virtual gamepad has, one for each `GamepadAxis` and `GamepadButton`
+const VIRTUAL_AXES: i32 = 6;
+const VIRTUAL_BUTTONS: i32 = SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_MAX as i32;
+
+/// A gamepad that was plugged in or unplugged while polling events
+pub(crate) enum GamepadHotplug {
+    Connected(usize),
+    Disconnected(usize),
+}
+
+/// update gamepad input from an SDL event, called by `begin_drawing`
+pub(crate) fn record_event(get: &mut Maylib, event: &Event) -> Option<GamepadHotplug> {
+    match *event {
+        Event::ControllerDeviceAdded { which, .. } => {
+            let controller = get.game_controller.as_ref()?.open(which).ok()?;
+            // Gamepads we opened ourselves get an added event too
+            if find_slot(get, controller.instance_id()).is_some() {
+                return None;
+            }
+            // A gamepad standing in for a recorded one goes back in the slot it was recorded in
+            let slot = get.playback.as_mut().and_then(|playback| playback.slots.remove(&which));
+            Some(GamepadHotplug::Connected(add_gamepad(get, controller, slot.is_some(), slot)))
+        }
+        Event::ControllerDeviceRemoved { which, .. } => {
+            let slot = find_slot(get, which)?;
+            get.gamepads[slot] = None;
+            Some(GamepadHotplug::Disconnected(slot))
+        }
+        Event::ControllerButtonDown { which, button, .. } => {
+            let slot = find_slot(get, which)?;
+            let gamepad = get.gamepads[slot].as_mut()?;
+            gamepad.buttons_down.insert(button.into());
+            None
+        }
+        Event::ControllerButtonUp { which, button, .. } => {
+            let slot = find_slot(get, which)?;
+            let gamepad = get.gamepads[slot].as_mut()?;
+            gamepad.buttons_down.remove(&button.into());
+            None
+        }
+        Event::ControllerAxisMotion { which, axis, value, .. } => {
+            let slot = find_slot(get, which)?;
+            let gamepad = get.gamepads[slot].as_mut()?;
+            // i16 goes one further down than up, so clamp to keep -1 to 1
+            gamepad.axes.insert(axis.into(), (value as f32 / i16::MAX as f32).max(-1.0));
+            None
+        }
+        _ => None,
+    }
+}
+
+/// make this frame's gamepad buttons the previous frame's, called by `begin_drawing` before polling
+pub(crate) fn begin_frame(get: &mut Maylib) {
+    for gamepad in get.gamepads.iter_mut().flatten() {
+        gamepad.previous_buttons_down.clone_from(&gamepad.buttons_down);
+    }
+}
+
+/// run the connected and disconnected callbacks, without holding the lock
+pub(crate) fn notify_hotplug(changes: &[GamepadHotplug]) {
+    if changes.is_empty() {
+        return;
+    }
+    let (mut connected, mut disconnected) = {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        (
+            std::mem::take(&mut get.gamepad_connected_callbacks),
+            std::mem::take(&mut get.gamepad_disconnected_callbacks),
+        )
+    };
+    for change in changes {
+        match *change {
+            GamepadHotplug::Connected(slot) => connected.iter_mut().for_each(|callback| callback(slot)),
+            GamepadHotplug::Disconnected(slot) => disconnected.iter_mut().for_each(|callback| callback(slot)),
+        }
+    }
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    // Keep any callbacks that were added while these ran
+    connected.append(&mut get.gamepad_connected_callbacks);
+    get.gamepad_connected_callbacks = connected;
+    disconnected.append(&mut get.gamepad_disconnected_callbacks);
+    get.gamepad_disconnected_callbacks = disconnected;
+}
+
+pub(crate) fn find_slot(get: &Maylib, instance_id: u32) -> Option<usize> {
+    get.gamepads.iter().position(|gamepad| {
+        gamepad
+            .as_ref()
+            .is_some_and(|gamepad| gamepad.controller.instance_id() == instance_id)
+    })
+}
+
+/// put a gamepad in `slot`, or the first free slot
+fn add_gamepad(
+    get: &mut Maylib,
+    controller: sdl2::controller::GameController,
+    is_virtual: bool,
+    slot: Option<usize>,
+) -> usize {
+    let gamepad = Gamepad {
+        controller,
+        buttons_down: HashSet::new(),
+        previous_buttons_down: HashSet::new(),
+        axes: HashMap::new(),
+        is_virtual,
+    };
+    if let Some(slot) = slot {
+        if get.gamepads.len() <= slot {
+            get.gamepads.resize_with(slot + 1, || None);
+        }
+        get.gamepads[slot] = Some(gamepad);
+        return slot;
+    }
+    match get.gamepads.iter().position(Option::is_none) {
+        Some(slot) => {
+            get.gamepads[slot] = Some(gamepad);
+            slot
+        }
+        None => {
+            get.gamepads.push(Some(gamepad));
+            get.gamepads.len() - 1
+        }
+    }
+}
+
+fn with_gamepad<T>(gamepad: usize, default: T, f: impl FnOnce(&Gamepad) -> T) -> T {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    match get.gamepads.get(gamepad) {
+        Some(Some(gamepad)) => f(gamepad),
+        _ => default,
+    }
+}
+
+/// check if a gamepad is connected in this slot. Slots start at 0 and are reused after a gamepad disconnects
+pub fn is_gamepad_available(gamepad: usize) -> bool {
+    with_gamepad(gamepad, false, |_| true)
+}
+
+/// get how many gamepads are connected
+pub fn get_gamepad_count() -> usize {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.gamepads.iter().flatten().count()
+}
+
+/// get the name of a gamepad, like "Xbox One Controller"
+pub fn get_gamepad_name(gamepad: usize) -> Option<String> {
+    with_gamepad(gamepad, None, |gamepad| Some(gamepad.controller.name()))
+}
+
+/// check if a gamepad button went down this frame
+pub fn is_gamepad_button_pressed(gamepad: usize, button: GamepadButton) -> bool {
+    with_gamepad(gamepad, false, |gamepad| {
+        gamepad.buttons_down.contains(&button) && !gamepad.previous_buttons_down.contains(&button)
+    })
+}
+
+/// check if a gamepad button is held down
+pub fn is_gamepad_button_down(gamepad: usize, button: GamepadButton) -> bool {
+    with_gamepad(gamepad, false, |gamepad| gamepad.buttons_down.contains(&button))
+}
+
+/// check if a gamepad button went up this frame
+pub fn is_gamepad_button_released(gamepad: usize, button: GamepadButton) -> bool {
+    with_gamepad(gamepad, false, |gamepad| {
+        !gamepad.buttons_down.contains(&button) && gamepad.previous_buttons_down.contains(&button)
+    })
+}
+
+/// check if a gamepad button is not held down. Also true when the gamepad isn't connected
+pub fn is_gamepad_button_up(gamepad: usize, button: GamepadButton) -> bool {
+    !is_gamepad_button_down(gamepad, button)
+}
+
+/// get where a stick or trigger is, from -1 to 1 for sticks and 0 to 1 for triggers.
+/// Movement inside the deadzone reads as 0
+pub fn get_gamepad_axis_movement(gamepad: usize, axis: GamepadAxis) -> f32 {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    match get.gamepads.get(gamepad) {
+        Some(Some(gamepad)) => axis_movement(&get, gamepad, axis),
+        _ => 0.0,
+    }
+}
+
+/// read an axis with the deadzone applied
+pub(crate) fn axis_movement(get: &Maylib, gamepad: &Gamepad, axis: GamepadAxis) -> f32 {
+    let value = gamepad.axes.get(&axis).copied().unwrap_or(0.0);
+    let deadzone = get.gamepad_deadzone;
+    if value.abs() <= deadzone {
+        return 0.0;
+    }
+    // Scale what is left so movement starts at 0 at the edge of the deadzone
+    value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+}
+
+/// set how far sticks and triggers must move before they read as moving, from 0 to 1. Defaults to 0.1
+pub fn set_gamepad_deadzone(deadzone: f32) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.gamepad_deadzone = deadzone.clamp(0.0, 0.99);
+}
+
+pub fn get_gamepad_deadzone() -> f32 {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.gamepad_deadzone
+}
+
+/// add gamepad mappings in the format of gamecontrollerdb.txt, and get how many were added.
+/// Lines for other platforms are skipped
+pub fn add_gamepad_mappings(mappings: &str) -> usize {
+    try_add_gamepad_mappings(mappings).expect("Can't add gamepad mappings")
+}
+
+pub fn try_add_gamepad_mappings(mappings: &str) -> Result<usize, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let game_controller = get
+        .game_controller
+        .as_ref()
+        .ok_or_else(|| Error::Sdl("gamepads are not supported".to_string()))?;
+    let added = game_controller
+        .load_mappings_from_read(&mut mappings.as_bytes())
+        .map_err(|e| Error::Sdl(e.to_string()))?;
+    Ok(added.max(0) as usize)
+}
+
+/// call `callback` with the slot of every gamepad that connects
+pub fn on_gamepad_connected(callback: impl FnMut(usize) + Send + 'static) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.gamepad_connected_callbacks.push(Box::new(callback));
+}
+
+/// call `callback` with the slot of every gamepad that disconnects
+pub fn on_gamepad_disconnected(callback: impl FnMut(usize) + Send + 'static) {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.gamepad_disconnected_callbacks.push(Box::new(callback));
+}
+
+/// connect a pretend gamepad that code can press buttons on, for tests. Returns its slot.
+/// It fills the slot straight away, and the connected callbacks and event come at the next `begin_drawing`
+pub fn attach_virtual_gamepad() -> usize {
+    try_attach_virtual_gamepad().expect("Can't attach virtual gamepad")
+}
+
+pub fn try_attach_virtual_gamepad() -> Result<usize, Error> {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    let game_controller = get
+        .game_controller
+        .as_ref()
+        .ok_or_else(|| Error::Sdl("gamepads are not supported".to_string()))?;
+    let index = attach_virtual_joystick()?;
+    let controller = game_controller
+        .open(index as u32)
+        .map_err(|e| Error::Sdl(e.to_string()))?;
+    let slot = add_gamepad(&mut get, controller, true, None);
+    // SDL's added event finds the slot already filled, so announce it here
+    get.gamepad_hotplugs.push(GamepadHotplug::Connected(slot));
+    recording::record_gamepad(&mut get, slot);
+    Ok(slot)
+}
+
+/// make SDL pretend a gamepad is plugged in, and get its device index
+pub(crate) fn attach_virtual_joystick() -> Result<i32, Error> {
+    // SAFETY: plain ints in, SDL reports failure as a negative index
+    let index = unsafe {
+        sdl2::sys::SDL_JoystickAttachVirtual(
+            SDL_JoystickType::SDL_JOYSTICK_TYPE_GAMECONTROLLER,
+            VIRTUAL_AXES,
+            VIRTUAL_BUTTONS,
+            0,
+        )
+    };
+    if index < 0 {
+        return Err(Error::Sdl(sdl2::get_error()));
+    }
+    Ok(index)
+}
+
+/// unplug a joystick made by `attach_virtual_joystick`
+pub(crate) fn detach_virtual_joystick(instance_id: u32) -> Result<(), Error> {
+    // Device indexes shift when an earlier device is unplugged, so find this one's index now
+    // SAFETY: plain ints in and out
+    let index = (0..unsafe { sdl2::sys::SDL_NumJoysticks() })
+        .find(|&index| unsafe { sdl2::sys::SDL_JoystickGetDeviceInstanceID(index) } == instance_id as i32)
+        .ok_or_else(|| Error::Sdl(format!("joystick {} is not plugged in", instance_id)))?;
+    // SAFETY: the index was just looked up, and SDL only detaches virtual joysticks
+    if unsafe { sdl2::sys::SDL_JoystickDetachVirtual(index) } < 0 {
+        return Err(Error::Sdl(sdl2::get_error()));
+    }
+    Ok(())
+}
+
+/// disconnect a gamepad made by `attach_virtual_gamepad`. The disconnected callbacks and event come at the next `begin_drawing`
+pub fn detach_virtual_gamepad(gamepad: usize) {
+    try_detach_virtual_gamepad(gamepad).expect("Can't detach virtual gamepad");
+}
+
+pub fn try_detach_virtual_gamepad(gamepad: usize) -> Result<(), Error> {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    let instance_id = virtual_instance_id(&get, gamepad)?;
+    // Keep the slot if SDL can't let go, so the gamepad can still be detached later
+    detach_virtual_joystick(instance_id)?;
+    get.gamepads[gamepad] = None;
+    // SDL's removed event finds the slot already empty, so announce it here
+    get.gamepad_hotplugs.push(GamepadHotplug::Disconnected(gamepad));
+    recording::record_gamepad(&mut get, gamepad);
+    Ok(())
+}
+
+/// press or release a button on a gamepad made by `attach_virtual_gamepad`. Takes effect at the next `begin_drawing`
+pub fn set_virtual_gamepad_button(gamepad: usize, button: GamepadButton, down: bool) {
+    try_set_virtual_gamepad_button(gamepad, button, down).expect("Can't set virtual gamepad button");
+}
+
+pub fn try_set_virtual_gamepad_button(gamepad: usize, button: GamepadButton, down: bool) -> Result<(), Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let joystick = virtual_joystick(&get, gamepad)?;
+    // Virtual gamepads map their buttons to SDL's button order, so the indexes line up
+    let button = sdl2::controller::Button::from(button) as i32;
+    // SAFETY: the joystick stays open while its gamepad is in a slot
+    if unsafe { sdl2::sys::SDL_JoystickSetVirtualButton(joystick, button, down as u8) } < 0 {
+        return Err(Error::Sdl(sdl2::get_error()));
+    }
+    Ok(())
+}
+
+/// move a stick or trigger on a gamepad made by `attach_virtual_gamepad`, from -1 to 1. Takes effect at the next `begin_drawing`
+pub fn set_virtual_gamepad_axis(gamepad: usize, axis: GamepadAxis, value: f32) {
+    try_set_virtual_gamepad_axis(gamepad, axis, value).expect("Can't set virtual gamepad axis");
+}
+
+pub fn try_set_virtual_gamepad_axis(gamepad: usize, axis: GamepadAxis, value: f32) -> Result<(), Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let joystick = virtual_joystick(&get, gamepad)?;
+    let axis = sdl2::controller::Axis::from(axis) as i32;
+    let value = (value.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+    // SAFETY: the joystick stays open while its gamepad is in a slot
+    if unsafe { sdl2::sys::SDL_JoystickSetVirtualAxis(joystick, axis, value) } < 0 {
+        return Err(Error::Sdl(sdl2::get_error()));
+    }
+    Ok(())
+}
+
+fn virtual_instance_id(get: &Maylib, gamepad: usize) -> Result<u32, Error> {
+    get.gamepads
+        .get(gamepad)
+        .and_then(Option::as_ref)
+        .filter(|gamepad| gamepad.is_virtual)
+        .map(|gamepad| gamepad.controller.instance_id())
+        .ok_or(Error::InvalidGamepad(gamepad))
+}
+
+fn virtual_joystick(get: &Maylib, gamepad: usize) -> Result<*mut sdl2::sys::SDL_Joystick, Error> {
+    let instance_id = virtual_instance_id(get, gamepad)?;
+    // SAFETY: looks up a joystick SDL already has open for this gamepad
+    let joystick = unsafe { sdl2::sys::SDL_JoystickFromInstanceID(instance_id as i32) };
+    if joystick.is_null() {
+        return Err(Error::Sdl(sdl2::get_error()));
+    }
+    Ok(joystick)
+}
@@ -1,31 +1,193 @@
-use crate::core::MAYLIB;
-use sdl2::image::{LoadSurface, LoadTexture};
+use crate::core::{get_current_window, try_get_current_window, MAYLIB};
+use crate::error::Error;
+use crate::types::{Color, WindowHandle};
+use sdl2::image::{ImageRWops, LoadTexture, SaveSurface};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rwops::RWops;
+use sdl2::surface::Surface;
+
+/// An RGBA image held in memory, 4 bytes per pixel
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+impl Image {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The raw RGBA bytes, row by row
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Get the color of one pixel, or `None` if it is outside the image
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = ((y * self.width + x) * 4) as usize;
+        Some(Color::new_alpha(
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ))
+    }
+}
+
+/// load a PNG, JPG, TIF or WEBP file into memory
+pub fn load_image(path: &str) -> Image {
+    try_load_image(path).expect("Can't load image")
+}
+
+pub fn try_load_image(path: &str) -> Result<Image, Error> {
+    try_load_image_bytes(&std::fs::read(path)?)
+}
+
+/// decode a PNG, JPG, TIF or WEBP file that is already in memory
+pub fn load_image_bytes(bytes: &[u8]) -> Image {
+    try_load_image_bytes(bytes).expect("Can't load image")
+}
+
+pub fn try_load_image_bytes(bytes: &[u8]) -> Result<Image, Error> {
+    let surface = RWops::from_bytes(bytes)
+        .and_then(|rw| rw.load())
+        .and_then(|surface| surface.convert_format(PixelFormatEnum::RGBA32))
+        .map_err(Error::Decode)?;
+    let (width, height) = surface.size();
+    let pitch = surface.pitch() as usize;
+    // Rows can be padded, so copy them one at a time
+    let pixels = surface.with_lock(|data| {
+        data.chunks(pitch)
+            .take(height as usize)
+            .flat_map(|row| &row[..width as usize * 4])
+            .copied()
+            .collect()
+    });
+    Ok(Image { width, height, pixels })
+}
 
 pub fn set_window_icon(path: &str) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .window
-        .set_icon(sdl2::surface::Surface::from_file(path).expect("Can't load image"));
+    get_current_window().set_icon(path);
+}
+
+pub fn try_set_window_icon(path: &str) -> Result<(), Error> {
+    try_get_current_window()?.try_set_icon(path)
 }
 
 pub fn draw_image(path: &str, x: i32, y: i32) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let window = get
-        .windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window");
-    let texture = window.texture.load_texture(path).expect("Can't load image");
-    let texture_query = texture.query();
-    // Create destination rectangle at (x, y) with the texture's width and height
-    let dst_rect = sdl2::rect::Rect::new(x, y, texture_query.width, texture_query.height);
-    window
-        .canvas
-        .copy(&texture, None, Some(dst_rect))
-        .expect("Can't copy image");
+    get_current_window().draw_image(path, x, y);
+}
+
+pub fn try_draw_image(path: &str, x: i32, y: i32) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_image(path, x, y)
+}
+
+/// save an image as a PNG
+pub fn export_image(image: &Image, path: &str) {
+    try_export_image(image, path).expect("Can't save image");
+}
+
+pub fn try_export_image(image: &Image, path: &str) -> Result<(), Error> {
+    let mut pixels = image.pixels.clone();
+    let surface = Surface::from_data(
+        &mut pixels,
+        image.width,
+        image.height,
+        image.width * 4,
+        PixelFormatEnum::RGBA32,
+    )
+    .map_err(Error::Sdl)?;
+    surface.save(path).map_err(Error::Sdl)
+}
+
+/// read back what has been drawn to the current window this frame.
+/// Call this before `end_drawing`, since presenting the frame discards it
+pub fn read_pixels() -> Image {
+    get_current_window().read_pixels()
+}
+
+pub fn try_read_pixels() -> Result<Image, Error> {
+    try_get_current_window()?.try_read_pixels()
+}
+
+/// save what has been drawn to the current window this frame as a PNG.
+/// Call this before `end_drawing`, since presenting the frame discards it
+pub fn take_screenshot(path: &str) {
+    get_current_window().take_screenshot(path);
+}
+
+pub fn try_take_screenshot(path: &str) -> Result<(), Error> {
+    try_get_current_window()?.try_take_screenshot(path)
+}
+
+impl WindowHandle {
+    pub fn set_icon(self, path: &str) {
+        self.try_set_icon(path).expect("Can't load image");
+    }
+
+    pub fn try_set_icon(self, path: &str) -> Result<(), Error> {
+        // Read the file ourselves so a missing file is an Io error, not a Decode error
+        let bytes = std::fs::read(path)?;
+        let icon = RWops::from_bytes(&bytes)
+            .and_then(|rw| rw.load())
+            .map_err(Error::Decode)?;
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?.window.set_icon(icon);
+        Ok(())
+    }
+
+    pub fn draw_image(self, path: &str, x: i32, y: i32) {
+        self.try_draw_image(path, x, y).expect("Can't load image");
+    }
+
+    pub fn try_draw_image(self, path: &str, x: i32, y: i32) -> Result<(), Error> {
+        let bytes = std::fs::read(path)?;
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        let texture = window
+            .texture
+            .load_texture_bytes(&bytes)
+            .map_err(Error::Decode)?;
+        let texture_query = texture.query();
+        // Create destination rectangle at (x, y) with the texture's width and height
+        let dst_rect = sdl2::rect::Rect::new(x, y, texture_query.width, texture_query.height);
+        window
+            .canvas
+            .copy(&texture, None, Some(dst_rect))
+            .map_err(Error::Sdl)
+    }
+
+    /// read back what has been drawn to this window this frame
+    pub fn read_pixels(self) -> Image {
+        self.try_read_pixels().expect("Can't read pixels")
+    }
+
+    pub fn try_read_pixels(self) -> Result<Image, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let canvas = &get.window(self)?.canvas;
+        let (width, height) = canvas.output_size().map_err(Error::Sdl)?;
+        let pixels = canvas
+            .read_pixels(None, PixelFormatEnum::RGBA32)
+            .map_err(Error::Sdl)?;
+        Ok(Image { width, height, pixels })
+    }
+
+    /// save what has been drawn to this window this frame as a PNG
+    pub fn take_screenshot(self, path: &str) {
+        self.try_take_screenshot(path).expect("Can't take screenshot");
+    }
+
+    pub fn try_take_screenshot(self, path: &str) -> Result<(), Error> {
+        try_export_image(&self.try_read_pixels()?, path)
+    }
 }
 
 // TODO: More image operations, ie. image_resize, image_stretch, dither, etc
@@ -0,0 +1,859 @@
+use crate::core::{get_current_window, try_get_current_window, MAYLIB};
+use crate::error::Error;
+use crate::image::Image;
+use crate::types::{Maylib, MouseButton, Rectangle, Scancode, SystemCursor, TextComposition, WindowHandle, WindowInput};
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::Mod;
+use sdl2::mouse::{Cursor, MouseWheelDirection};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::surface::Surface;
+use std::path::PathBuf;
+
+/// update each window's input from an SDL event, called by `begin_drawing`
+pub(crate) fn record_event(get: &mut Maylib, event: &Event) {
+    let windows = &mut get.windows;
+    match *event {
+        Event::KeyDown { window_id, scancode: Some(scancode), repeat, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                let scancode = Scancode::from(scancode);
+                window.input.keys_down.insert(scancode);
+                if repeat {
+                    window.input.keys_repeated.insert(scancode);
+                } else {
+                    window.input.key_queue.push_back(scancode);
+                }
+            }
+        }
+        Event::KeyUp { window_id, scancode: Some(scancode), .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.keys_down.remove(&Scancode::from(scancode));
+            }
+        }
+        Event::TextInput { window_id, ref text, .. } => {
+            if let Some(window) = windows.get_mut(&window_id).filter(|w| w.input.text_input) {
+                window.input.char_queue.extend(text.chars());
+                window.input.composition = None;
+            }
+        }
+        Event::TextEditing { window_id, ref text, start, length, .. } => {
+            if let Some(window) = windows.get_mut(&window_id).filter(|w| w.input.text_input) {
+                // An empty composition means the IME was cancelled or committed
+                window.input.composition = (!text.is_empty()).then(|| TextComposition {
+                    text: text.clone(),
+                    cursor: start.max(0) as usize,
+                    selection_length: length.max(0) as usize,
+                });
+            }
+        }
+        Event::DropBegin { window_id, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                // A new drop replaces one that was never loaded
+                window.input.dropped_files.clear();
+                window.input.dropped_text.clear();
+                window.input.drop_in_progress = true;
+            }
+        }
+        Event::DropFile { window_id, ref filename, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.dropped_files.push(PathBuf::from(filename));
+            }
+        }
+        Event::DropText { window_id, ref filename, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.dropped_text.push(filename.clone());
+            }
+        }
+        Event::DropComplete { window_id, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.drop_in_progress = false;
+            }
+        }
+        Event::MouseMotion { window_id, x, y, xrel, yrel, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.mouse_x = x;
+                window.input.mouse_y = y;
+                window.input.mouse_delta.0 += xrel;
+                window.input.mouse_delta.1 += yrel;
+            }
+        }
+        Event::MouseWheel { window_id, direction, precise_x, precise_y, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                // Natural scrolling flips the values, flip them back so up is always positive
+                let sign = match direction {
+                    MouseWheelDirection::Flipped => -1.0,
+                    _ => 1.0,
+                };
+                window.input.wheel_move.0 += precise_x * sign;
+                window.input.wheel_move.1 += precise_y * sign;
+            }
+        }
+        Event::MouseButtonDown { window_id, mouse_btn, x, y, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.buttons_down.insert(mouse_btn);
+                window.input.mouse_x = x;
+                window.input.mouse_y = y;
+            }
+        }
+        Event::MouseButtonUp { window_id, mouse_btn, x, y, .. } => {
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.buttons_down.remove(&mouse_btn);
+                window.input.mouse_x = x;
+                window.input.mouse_y = y;
+            }
+        }
+        Event::Window { window_id, win_event: WindowEvent::Enter, .. } => {
+            // SDL has one cursor for the whole app, so swap it as the mouse moves between windows
+            let cursor = windows
+                .get(&window_id)
+                .and_then(|window| window.cursor.as_ref().or(get.default_cursor.as_ref()));
+            if let Some(cursor) = cursor {
+                cursor.set();
+            }
+        }
+        Event::Window { window_id, win_event: WindowEvent::FocusLost, .. } => {
+            // The key up events go to whichever window has focus now
+            if let Some(window) = windows.get_mut(&window_id) {
+                window.input.keys_down.clear();
+            }
+        }
+        _ => {}
+    }
+}
+
+impl WindowInput {
+    /// make this frame's input the previous frame's, called by `begin_drawing` before polling
+    pub(crate) fn begin_frame(&mut self) {
+        self.previous_keys_down.clone_from(&self.keys_down);
+        self.previous_buttons_down.clone_from(&self.buttons_down);
+        self.keys_repeated.clear();
+        self.key_queue.clear();
+        self.char_queue.clear();
+        self.mouse_delta = (0, 0);
+        self.wheel_move = (0.0, 0.0);
+    }
+
+    fn is_either_down(&self, left: Scancode, right: Scancode) -> bool {
+        self.keys_down.contains(&left) || self.keys_down.contains(&right)
+    }
+}
+
+/// check if a key went down this frame in the current window
+pub fn is_key_pressed(key: impl Into<Scancode>) -> bool {
+    get_current_window().is_key_pressed(key)
+}
+
+pub fn try_is_key_pressed(key: impl Into<Scancode>) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_key_pressed(key)
+}
+
+/// check if the OS sent a key repeat this frame in the current window
+pub fn is_key_pressed_repeat(key: impl Into<Scancode>) -> bool {
+    get_current_window().is_key_pressed_repeat(key)
+}
+
+pub fn try_is_key_pressed_repeat(key: impl Into<Scancode>) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_key_pressed_repeat(key)
+}
+
+/// check if a key is held down in the current window
+pub fn is_key_down(key: impl Into<Scancode>) -> bool {
+    get_current_window().is_key_down(key)
+}
+
+pub fn try_is_key_down(key: impl Into<Scancode>) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_key_down(key)
+}
+
+/// check if a key went up this frame in the current window
+pub fn is_key_released(key: impl Into<Scancode>) -> bool {
+    get_current_window().is_key_released(key)
+}
+
+pub fn try_is_key_released(key: impl Into<Scancode>) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_key_released(key)
+}
+
+/// check if a key is not held down in the current window
+pub fn is_key_up(key: impl Into<Scancode>) -> bool {
+    get_current_window().is_key_up(key)
+}
+
+pub fn try_is_key_up(key: impl Into<Scancode>) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_key_up(key)
+}
+
+/// get the next key that went down this frame in the current window, oldest first. Key repeats are skipped
+pub fn get_key_pressed() -> Option<Scancode> {
+    get_current_window().next_key_pressed()
+}
+
+pub fn try_get_key_pressed() -> Result<Option<Scancode>, Error> {
+    try_get_current_window()?.try_next_key_pressed()
+}
+
+/// check if either shift key is held down in the current window
+pub fn is_shift_down() -> bool {
+    get_current_window().is_shift_down()
+}
+
+pub fn try_is_shift_down() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_shift_down()
+}
+
+/// check if either ctrl key is held down in the current window
+pub fn is_ctrl_down() -> bool {
+    get_current_window().is_ctrl_down()
+}
+
+pub fn try_is_ctrl_down() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_ctrl_down()
+}
+
+/// check if either alt key is held down in the current window
+pub fn is_alt_down() -> bool {
+    get_current_window().is_alt_down()
+}
+
+pub fn try_is_alt_down() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_alt_down()
+}
+
+/// check if either super key (windows or command) is held down in the current window
+pub fn is_super_down() -> bool {
+    get_current_window().is_super_down()
+}
+
+pub fn try_is_super_down() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_super_down()
+}
+
+/// check if caps lock is on
+pub fn is_caps_lock_on() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.video.sdl().keyboard().mod_state().contains(Mod::CAPSMOD)
+}
+
+/// check if num lock is on
+pub fn is_num_lock_on() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.video.sdl().keyboard().mod_state().contains(Mod::NUMMOD)
+}
+
+/// start collecting typed characters for a window, and show the IME or on-screen keyboard if there is one
+pub fn start_text_input(window: WindowHandle) {
+    try_start_text_input(window).expect("Window handle should be valid");
+}
+
+pub fn try_start_text_input(window: WindowHandle) -> Result<(), Error> {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    get.window(window)?;
+    // SDL only has one text input, so only one window can have it
+    for (id, other) in get.windows.iter_mut() {
+        other.input.text_input = *id == window.id;
+        if *id != window.id {
+            other.input.composition = None;
+        }
+    }
+    get.video.text_input().start();
+    Ok(())
+}
+
+/// stop collecting typed characters and hide the IME
+pub fn stop_text_input() {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    for window in get.windows.values_mut() {
+        window.input.text_input = false;
+        window.input.composition = None;
+    }
+    get.video.text_input().stop();
+}
+
+/// check if any window is collecting typed characters
+pub fn is_text_input_active() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.windows.values().any(|window| window.input.text_input)
+}
+
+/// tell the IME where the text being typed is, in window coordinates, so it can put its candidate list next to it
+pub fn set_text_input_rect(rect: Rectangle) {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.video.text_input().set_rect(sdl2::rect::Rect::new(
+        rect.x,
+        rect.y,
+        rect.width,
+        rect.height,
+    ));
+}
+
+/// get the next character typed this frame in the current window, oldest first. Needs `start_text_input`
+pub fn get_char_pressed() -> Option<char> {
+    get_current_window().next_char_pressed()
+}
+
+pub fn try_get_char_pressed() -> Result<Option<char>, Error> {
+    try_get_current_window()?.try_next_char_pressed()
+}
+
+/// get the text the IME is composing in the current window, to draw it where the player is typing
+pub fn get_text_composition() -> Option<TextComposition> {
+    get_current_window().text_composition()
+}
+
+pub fn try_get_text_composition() -> Result<Option<TextComposition>, Error> {
+    try_get_current_window()?.try_text_composition()
+}
+
+/// get how far the mouse moved in the current window this frame. Keeps working in relative mouse mode
+pub fn get_mouse_delta() -> (i32, i32) {
+    get_current_window().mouse_delta()
+}
+
+pub fn try_get_mouse_delta() -> Result<(i32, i32), Error> {
+    try_get_current_window()?.try_mouse_delta()
+}
+
+/// get how far the mouse wheel scrolled in the current window this frame. Up is positive
+pub fn get_mouse_wheel_move() -> f32 {
+    get_current_window().mouse_wheel_move()
+}
+
+pub fn try_get_mouse_wheel_move() -> Result<f32, Error> {
+    try_get_current_window()?.try_mouse_wheel_move()
+}
+
+/// get how far the mouse wheel scrolled in the current window this frame, horizontally and vertically.
+/// Trackpads and free-spinning wheels give fractions of a notch
+pub fn get_mouse_wheel_move_v() -> (f32, f32) {
+    get_current_window().mouse_wheel_move_v()
+}
+
+pub fn try_get_mouse_wheel_move_v() -> Result<(f32, f32), Error> {
+    try_get_current_window()?.try_mouse_wheel_move_v()
+}
+
+/// move the mouse to a position in the current window
+pub fn set_mouse_position(x: i32, y: i32) {
+    get_current_window().set_mouse_position(x, y);
+}
+
+pub fn try_set_mouse_position(x: i32, y: i32) -> Result<(), Error> {
+    try_get_current_window()?.try_set_mouse_position(x, y)
+}
+
+/// confine the mouse to the current window, or let it leave again
+pub fn set_mouse_grab(grabbed: bool) {
+    get_current_window().set_mouse_grab(grabbed);
+}
+
+pub fn try_set_mouse_grab(grabbed: bool) -> Result<(), Error> {
+    try_get_current_window()?.try_set_mouse_grab(grabbed)
+}
+
+/// check if the mouse is confined to the current window
+pub fn is_mouse_grabbed() -> bool {
+    get_current_window().is_mouse_grabbed()
+}
+
+pub fn try_is_mouse_grabbed() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_mouse_grabbed()
+}
+
+/// hide the cursor and report only mouse deltas, for camera controls. The mouse stays in the focused window
+pub fn set_relative_mouse_mode(enabled: bool) {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.mouse.set_relative_mouse_mode(enabled);
+}
+
+/// check if relative mouse mode is on
+pub fn is_relative_mouse_mode() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.mouse.relative_mouse_mode()
+}
+
+/// get the mouse position on the desktop, across all monitors
+pub fn get_global_mouse_position() -> (i32, i32) {
+    let (mut x, mut y) = (0, 0);
+    // SAFETY: SDL only writes the position into the two ints
+    unsafe {
+        sdl2::sys::SDL_GetGlobalMouseState(&mut x, &mut y);
+    }
+    (x, y)
+}
+
+/// move the mouse to a position on the desktop
+pub fn set_global_mouse_position(x: i32, y: i32) {
+    try_set_global_mouse_position(x, y).expect("Should be able to move the mouse");
+}
+
+pub fn try_set_global_mouse_position(x: i32, y: i32) -> Result<(), Error> {
+    // SAFETY: takes plain ints, SDL reports failure through the return value
+    if unsafe { sdl2::sys::SDL_WarpMouseGlobal(x, y) } == 0 {
+        Ok(())
+    } else {
+        Err(Error::Sdl(sdl2::get_error()))
+    }
+}
+
+/// change the cursor shown over the current window
+pub fn set_mouse_cursor(cursor: SystemCursor) {
+    get_current_window().set_mouse_cursor(cursor);
+}
+
+pub fn try_set_mouse_cursor(cursor: SystemCursor) -> Result<(), Error> {
+    try_get_current_window()?.try_set_mouse_cursor(cursor)
+}
+
+/// show an image as the cursor over the current window. `hot_x` and `hot_y` are the pixel that does the clicking
+pub fn set_custom_cursor(image: &Image, hot_x: i32, hot_y: i32) {
+    get_current_window().set_custom_cursor(image, hot_x, hot_y);
+}
+
+pub fn try_set_custom_cursor(image: &Image, hot_x: i32, hot_y: i32) -> Result<(), Error> {
+    try_get_current_window()?.try_set_custom_cursor(image, hot_x, hot_y)
+}
+
+/// check if files were dropped on the current window and haven't been loaded yet
+pub fn is_file_dropped() -> bool {
+    get_current_window().is_file_dropped()
+}
+
+pub fn try_is_file_dropped() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_file_dropped()
+}
+
+/// take the paths of the files dropped on the current window. Each drop is only returned once
+pub fn load_dropped_files() -> Vec<PathBuf> {
+    get_current_window().load_dropped_files()
+}
+
+pub fn try_load_dropped_files() -> Result<Vec<PathBuf>, Error> {
+    try_get_current_window()?.try_load_dropped_files()
+}
+
+/// check if text was dropped on the current window and hasn't been loaded yet
+pub fn is_text_dropped() -> bool {
+    get_current_window().is_text_dropped()
+}
+
+pub fn try_is_text_dropped() -> Result<bool, Error> {
+    try_get_current_window()?.try_is_text_dropped()
+}
+
+/// take the text dropped on the current window. Each drop is only returned once
+pub fn load_dropped_text() -> Vec<String> {
+    get_current_window().load_dropped_text()
+}
+
+pub fn try_load_dropped_text() -> Result<Vec<String>, Error> {
+    try_get_current_window()?.try_load_dropped_text()
+}
+
+/// check if a mouse button went down this frame in the current window
+pub fn is_mouse_button_pressed(button: MouseButton) -> bool {
+    get_current_window().is_mouse_button_pressed(button)
+}
+
+pub fn try_is_mouse_button_pressed(button: MouseButton) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_mouse_button_pressed(button)
+}
+
+/// check if a mouse button is held down in the current window
+pub fn is_mouse_button_down(button: MouseButton) -> bool {
+    get_current_window().is_mouse_button_down(button)
+}
+
+pub fn try_is_mouse_button_down(button: MouseButton) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_mouse_button_down(button)
+}
+
+/// check if a mouse button went up this frame in the current window
+pub fn is_mouse_button_released(button: MouseButton) -> bool {
+    get_current_window().is_mouse_button_released(button)
+}
+
+pub fn try_is_mouse_button_released(button: MouseButton) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_mouse_button_released(button)
+}
+
+/// check if a mouse button is not held down in the current window
+pub fn is_mouse_button_up(button: MouseButton) -> bool {
+    get_current_window().is_mouse_button_up(button)
+}
+
+pub fn try_is_mouse_button_up(button: MouseButton) -> Result<bool, Error> {
+    try_get_current_window()?.try_is_mouse_button_up(button)
+}
+
+impl WindowHandle {
+    /// run `f` on this window's input, or on no keys at all if the window doesn't have focus
+    fn with_keys<T>(self, f: impl FnOnce(&WindowInput) -> T) -> Result<T, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window(self)?;
+        if window.focused {
+            Ok(f(&window.input))
+        } else {
+            Ok(f(&WindowInput::default()))
+        }
+    }
+
+    fn with_input<T>(self, f: impl FnOnce(&WindowInput) -> T) -> Result<T, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(f(&get.window(self)?.input))
+    }
+
+    pub fn is_key_pressed(self, key: impl Into<Scancode>) -> bool {
+        self.try_is_key_pressed(key)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_key_pressed(self, key: impl Into<Scancode>) -> Result<bool, Error> {
+        let scancode: Scancode = key.into();
+        self.with_keys(|input| {
+            input.keys_down.contains(&scancode) && !input.previous_keys_down.contains(&scancode)
+        })
+    }
+
+    pub fn is_key_pressed_repeat(self, key: impl Into<Scancode>) -> bool {
+        self.try_is_key_pressed_repeat(key)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_key_pressed_repeat(self, key: impl Into<Scancode>) -> Result<bool, Error> {
+        let scancode: Scancode = key.into();
+        self.with_keys(|input| input.keys_repeated.contains(&scancode))
+    }
+
+    pub fn is_key_down(self, key: impl Into<Scancode>) -> bool {
+        self.try_is_key_down(key)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_key_down(self, key: impl Into<Scancode>) -> Result<bool, Error> {
+        let scancode: Scancode = key.into();
+        self.with_keys(|input| input.keys_down.contains(&scancode))
+    }
+
+    pub fn is_key_released(self, key: impl Into<Scancode>) -> bool {
+        self.try_is_key_released(key)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_key_released(self, key: impl Into<Scancode>) -> Result<bool, Error> {
+        let scancode: Scancode = key.into();
+        self.with_keys(|input| {
+            !input.keys_down.contains(&scancode) && input.previous_keys_down.contains(&scancode)
+        })
+    }
+
+    pub fn is_key_up(self, key: impl Into<Scancode>) -> bool {
+        self.try_is_key_up(key)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_key_up(self, key: impl Into<Scancode>) -> Result<bool, Error> {
+        let scancode: Scancode = key.into();
+        self.with_keys(|input| !input.keys_down.contains(&scancode))
+    }
+
+    pub fn next_key_pressed(self) -> Option<Scancode> {
+        self.try_next_key_pressed()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_next_key_pressed(self) -> Result<Option<Scancode>, Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window_mut(self)?.input.key_queue.pop_front())
+    }
+
+    pub fn is_shift_down(self) -> bool {
+        self.try_is_shift_down()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_shift_down(self) -> Result<bool, Error> {
+        self.with_keys(|input| input.is_either_down(Scancode::LShift, Scancode::RShift))
+    }
+
+    pub fn is_ctrl_down(self) -> bool {
+        self.try_is_ctrl_down()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_ctrl_down(self) -> Result<bool, Error> {
+        self.with_keys(|input| input.is_either_down(Scancode::LCtrl, Scancode::RCtrl))
+    }
+
+    pub fn is_alt_down(self) -> bool {
+        self.try_is_alt_down()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_alt_down(self) -> Result<bool, Error> {
+        self.with_keys(|input| input.is_either_down(Scancode::LAlt, Scancode::RAlt))
+    }
+
+    pub fn is_super_down(self) -> bool {
+        self.try_is_super_down()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_super_down(self) -> Result<bool, Error> {
+        self.with_keys(|input| input.is_either_down(Scancode::LGui, Scancode::RGui))
+    }
+
+    pub fn start_text_input(self) {
+        start_text_input(self);
+    }
+
+    pub fn try_start_text_input(self) -> Result<(), Error> {
+        try_start_text_input(self)
+    }
+
+    pub fn next_char_pressed(self) -> Option<char> {
+        self.try_next_char_pressed()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_next_char_pressed(self) -> Result<Option<char>, Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window_mut(self)?.input.char_queue.pop_front())
+    }
+
+    pub fn text_composition(self) -> Option<TextComposition> {
+        self.try_text_composition()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_text_composition(self) -> Result<Option<TextComposition>, Error> {
+        self.with_input(|input| input.composition.clone())
+    }
+
+    pub fn is_mouse_button_pressed(self, button: MouseButton) -> bool {
+        self.try_is_mouse_button_pressed(button)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_mouse_button_pressed(self, button: MouseButton) -> Result<bool, Error> {
+        let button = button.into();
+        self.with_input(|input| {
+            input.buttons_down.contains(&button) && !input.previous_buttons_down.contains(&button)
+        })
+    }
+
+    pub fn is_mouse_button_down(self, button: MouseButton) -> bool {
+        self.try_is_mouse_button_down(button)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_mouse_button_down(self, button: MouseButton) -> Result<bool, Error> {
+        self.with_input(|input| input.buttons_down.contains(&button.into()))
+    }
+
+    pub fn is_mouse_button_released(self, button: MouseButton) -> bool {
+        self.try_is_mouse_button_released(button)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_mouse_button_released(self, button: MouseButton) -> Result<bool, Error> {
+        let button = button.into();
+        self.with_input(|input| {
+            !input.buttons_down.contains(&button) && input.previous_buttons_down.contains(&button)
+        })
+    }
+
+    pub fn is_mouse_button_up(self, button: MouseButton) -> bool {
+        self.try_is_mouse_button_up(button)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_mouse_button_up(self, button: MouseButton) -> Result<bool, Error> {
+        self.with_input(|input| !input.buttons_down.contains(&button.into()))
+    }
+
+    /// check if a key is held down while this window has focus. Same as `is_key_down`
+    pub fn key_pressed(self, key: impl Into<Scancode>) -> bool {
+        self.is_key_down(key)
+    }
+
+    pub fn try_key_pressed(self, key: impl Into<Scancode>) -> Result<bool, Error> {
+        self.try_is_key_down(key)
+    }
+
+    /// check if a mouse button was pressed over this window and is still held down. Same as `is_mouse_button_down`
+    pub fn mouse_button_pressed(self, button: MouseButton) -> bool {
+        self.is_mouse_button_down(button)
+    }
+
+    pub fn try_mouse_button_pressed(self, button: MouseButton) -> Result<bool, Error> {
+        self.try_is_mouse_button_down(button)
+    }
+
+    /// get the mouse x relative to this window, as of the last time the mouse was over it
+    pub fn mouse_x(self) -> i32 {
+        self.mouse_position().0
+    }
+
+    /// get the mouse y relative to this window, as of the last time the mouse was over it
+    pub fn mouse_y(self) -> i32 {
+        self.mouse_position().1
+    }
+
+    /// get the mouse position relative to this window, as of the last time the mouse was over it
+    pub fn mouse_position(self) -> (i32, i32) {
+        self.try_mouse_position()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_mouse_position(self) -> Result<(i32, i32), Error> {
+        self.with_input(|input| (input.mouse_x, input.mouse_y))
+    }
+
+    pub fn mouse_delta(self) -> (i32, i32) {
+        self.try_mouse_delta()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_mouse_delta(self) -> Result<(i32, i32), Error> {
+        self.with_input(|input| input.mouse_delta)
+    }
+
+    pub fn mouse_wheel_move(self) -> f32 {
+        self.mouse_wheel_move_v().1
+    }
+
+    pub fn try_mouse_wheel_move(self) -> Result<f32, Error> {
+        Ok(self.try_mouse_wheel_move_v()?.1)
+    }
+
+    pub fn mouse_wheel_move_v(self) -> (f32, f32) {
+        self.try_mouse_wheel_move_v()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_mouse_wheel_move_v(self) -> Result<(f32, f32), Error> {
+        self.with_input(|input| input.wheel_move)
+    }
+
+    pub fn set_mouse_position(self, x: i32, y: i32) {
+        self.try_set_mouse_position(x, y)
+            .expect("Window handle should be valid");
+    }
+
+    pub fn try_set_mouse_position(self, x: i32, y: i32) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let get = &mut *get;
+        let window = get.windows.get_mut(&self.id).ok_or(Error::InvalidWindow(self.id))?;
+        get.mouse.warp_mouse_in_window(&window.window, x, y);
+        window.input.mouse_x = x;
+        window.input.mouse_y = y;
+        Ok(())
+    }
+
+    pub fn set_mouse_grab(self, grabbed: bool) {
+        self.try_set_mouse_grab(grabbed)
+            .expect("Window handle should be valid");
+    }
+
+    pub fn try_set_mouse_grab(self, grabbed: bool) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?.window.set_mouse_grab(grabbed);
+        Ok(())
+    }
+
+    pub fn is_mouse_grabbed(self) -> bool {
+        self.try_is_mouse_grabbed()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_mouse_grabbed(self) -> Result<bool, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        Ok(get.window(self)?.window.mouse_grab())
+    }
+
+    pub fn set_mouse_cursor(self, cursor: SystemCursor) {
+        self.try_set_mouse_cursor(cursor)
+            .expect("Can't set cursor");
+    }
+
+    pub fn try_set_mouse_cursor(self, cursor: SystemCursor) -> Result<(), Error> {
+        let cursor = Cursor::from_system(cursor.into()).map_err(Error::Sdl)?;
+        self.try_use_cursor(cursor)
+    }
+
+    pub fn set_custom_cursor(self, image: &Image, hot_x: i32, hot_y: i32) {
+        self.try_set_custom_cursor(image, hot_x, hot_y)
+            .expect("Can't set cursor");
+    }
+
+    pub fn try_set_custom_cursor(self, image: &Image, hot_x: i32, hot_y: i32) -> Result<(), Error> {
+        let mut pixels = image.pixels().to_vec();
+        let surface = Surface::from_data(
+            &mut pixels,
+            image.width(),
+            image.height(),
+            image.width() * 4,
+            PixelFormatEnum::RGBA32,
+        )
+        .map_err(Error::Sdl)?;
+        let cursor = Cursor::from_surface(surface, hot_x, hot_y).map_err(Error::Sdl)?;
+        self.try_use_cursor(cursor)
+    }
+
+    fn try_use_cursor(self, cursor: Cursor) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let window = get.window_mut(self)?;
+        if window.hovered {
+            cursor.set();
+        }
+        window.cursor = Some(cursor);
+        Ok(())
+    }
+
+    /// check if files were dropped on this window and haven't been loaded yet.
+    /// Stays false until every file in the drop has arrived
+    pub fn is_file_dropped(self) -> bool {
+        self.try_is_file_dropped()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_file_dropped(self) -> Result<bool, Error> {
+        self.with_input(|input| !input.drop_in_progress && !input.dropped_files.is_empty())
+    }
+
+    pub fn load_dropped_files(self) -> Vec<PathBuf> {
+        self.try_load_dropped_files()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_load_dropped_files(self) -> Result<Vec<PathBuf>, Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let input = &mut get.window_mut(self)?.input;
+        if input.drop_in_progress {
+            return Ok(Vec::new());
+        }
+        Ok(std::mem::take(&mut input.dropped_files))
+    }
+
+    pub fn is_text_dropped(self) -> bool {
+        self.try_is_text_dropped()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_is_text_dropped(self) -> Result<bool, Error> {
+        self.with_input(|input| !input.drop_in_progress && !input.dropped_text.is_empty())
+    }
+
+    pub fn load_dropped_text(self) -> Vec<String> {
+        self.try_load_dropped_text()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_load_dropped_text(self) -> Result<Vec<String>, Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let input = &mut get.window_mut(self)?.input;
+        if input.drop_in_progress {
+            return Ok(Vec::new());
+        }
+        Ok(std::mem::take(&mut input.dropped_text))
+    }
+}
@@ -1,24 +1,51 @@
+pub mod actions;
 pub mod audio;
 pub mod core;
+pub mod error;
+pub mod events;
+pub mod gamepad;
 pub mod image;
+pub mod input;
+pub mod monitor;
+pub mod random;
+pub mod recording;
 pub mod shapes;
 pub mod text;
+pub mod touch;
 pub mod types;
 
+pub use error::Error;
+pub use types::Event;
+
 #[cfg(test)]
 pub mod test {
-    use std::os::windows;
+    use crate::actions::{bind_action, bind_axis_gamepad, bind_axis_keys, clear_action, clear_axis, get_action_bindings, get_axis_value, get_listened_input, is_action_pressed, is_listening_for_input, listen_for_input, load_bindings, save_bindings, try_is_action_pressed};
     use crate::audio::play_sound;
-    use crate::core::{begin_drawing, clear_background, end_drawing, init_window, switch_window, window_should_close};
+    use crate::core::{MAYLIB, begin_drawing, get_clipboard_text, has_clipboard_text, set_clipboard_text, cursor_hidden, hide_cursor, show_cursor, clear_background, close_after_frames, close_maylib, end_drawing, get_current_window, init_maylib_headless, init_window, on_shutdown, WindowBuilder, set_frame_limit, on_window_closed, switch_window, try_get_current_window, window_should_close};
     use crate::text::{draw_text, load_font_bytes};
-    use crate::types::{Color, Maylib};
+    use crate::types::{Color, FrameLimit, GamepadAxis, GamepadButton, Gesture, GestureState, InputBinding, Keycode, MouseButton, Scancode, TouchPoint};
+    use crate::events::{convert, poll_events, wait_event_timeout};
+    use crate::gamepad::{attach_virtual_gamepad, detach_virtual_gamepad, get_gamepad_axis_movement, is_gamepad_available, is_gamepad_button_down, is_gamepad_button_up, on_gamepad_connected, on_gamepad_disconnected, set_virtual_gamepad_axis, set_virtual_gamepad_button};
+    use crate::image::load_image_bytes;
+    use crate::input::{is_text_input_active, start_text_input, stop_text_input};
+    use crate::monitor::{get_monitor_bounds, get_monitor_count};
+    use crate::random::{choose_random, choose_random_weighted, get_perlin_noise, get_random_i32, get_random_f64, try_get_random_f64, get_random_normal, get_random_seed, get_random_unit_vector, load_random_sequence, set_random_seed};
+    use crate::recording::{is_playing_back, is_recording, start_playback, start_recording, stop_recording, try_start_playback};
+    use crate::shapes::draw_rectangle;
+    use sdl2::event::Event;
+    use std::sync::{Arc, Mutex};
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Tests run headless so they work in CI, and stop on their own after a few frames
 
     #[test]
     pub fn text_test() {
+        init_maylib_headless();
         let window = init_window("Text Test", 640, 480)
             .expect("Code in tests should be correct");
         let fira = load_font_bytes(include_bytes!(".././fira-code.ttf"));
         switch_window(window);
+        close_after_frames(10);
         while !window_should_close() {
             begin_drawing();
 
@@ -27,14 +54,17 @@ pub mod test {
 
             end_drawing();
         }
+        window.close();
     }
 
     #[test]
     pub fn audio_test() {
+        init_maylib_headless();
         let window = init_window("Audio Test", 500, 500)
             .expect("Code in tests should be correct");
         switch_window(window);
         play_sound("./test-beep.mp3");
+        close_after_frames(10);
         while !window_should_close() {
             begin_drawing();
 
@@ -42,5 +72,673 @@ pub mod test {
 
             end_drawing();
         }
+        window.close();
+    }
+
+    #[test]
+    pub fn multi_window_test() {
+        init_maylib_headless();
+        let left = init_window("Left Window", 400, 300)
+            .expect("Code in tests should be correct");
+        let right = init_window("Right Window", 400, 300)
+            .expect("Code in tests should be correct");
+        switch_window(left);
+        close_after_frames(10);
+        while !left.should_close() && !right.should_close() {
+            begin_drawing();
+
+            left.clear_background(Color::MayGray);
+            left.draw_rectangle(50, 50, 100, 100, Color::Red);
+
+            right.clear_background(Color::RayWhite);
+            right.draw_circle(200, 150, 50, Color::Blue);
+
+            // Free functions still draw into the current window
+            draw_rectangle(200, 50, 100, 100, Color::Lime);
+
+            end_drawing();
+        }
+        left.close();
+        right.close();
+    }
+
+    #[test]
+    pub fn close_after_frames_test() {
+        init_maylib_headless();
+        let window = init_window("Frame Count Test", 320, 240)
+            .expect("Code in tests should be correct");
+        close_after_frames(5);
+        let mut frames = 0;
+        while !window.should_close() {
+            begin_drawing();
+            window.clear_background(Color::Black);
+            end_drawing();
+            frames += 1;
+        }
+        assert_eq!(frames, 5);
+        window.close();
+
+        // A window opened after the countdown isn't closed by it
+        let later = init_window("After Countdown", 320, 240)
+            .expect("Code in tests should be correct");
+        for _ in 0..3 {
+            begin_drawing();
+            end_drawing();
+        }
+        assert!(!later.should_close());
+        later.close();
+    }
+
+    #[test]
+    pub fn read_pixels_test() {
+        init_maylib_headless();
+        let window = init_window("Read Pixels Test", 100, 100)
+            .expect("Code in tests should be correct");
+        begin_drawing();
+        window.clear_background(Color::Black);
+        window.draw_rectangle(10, 10, 20, 20, Color::Red);
+        let image = window.read_pixels();
+        end_drawing();
+        assert_eq!((image.width(), image.height()), (100, 100));
+        assert_eq!(image.get_pixel(15, 15), Some(Color::Red));
+        assert_eq!(image.get_pixel(50, 50), Some(Color::Black));
+        assert_eq!(image.get_pixel(100, 0), None);
+        window.close();
+    }
+
+    #[test]
+    pub fn shutdown_test() {
+        init_maylib_headless();
+        let window = init_window("Shutdown Test", 100, 100)
+            .expect("Code in tests should be correct");
+        let ran = Arc::new(AtomicBool::new(false));
+        let hook_ran = ran.clone();
+        on_shutdown(move || hook_ran.store(true, Ordering::SeqCst));
+        close_maylib();
+        assert!(ran.load(Ordering::SeqCst));
+        assert!(window.try_should_close().is_err());
+    }
+
+    #[test]
+    pub fn monitor_test() {
+        init_maylib_headless();
+        assert!(get_monitor_count() >= 1);
+        let bounds = get_monitor_bounds(0);
+        assert!(bounds.width > 0 && bounds.height > 0);
+        let window = init_window("Monitor Test", 100, 100)
+            .expect("Code in tests should be correct");
+        window.move_to_monitor(0);
+        assert_eq!(window.monitor(), 0);
+        window.close();
+    }
+
+    #[test]
+    pub fn close_lifecycle_test() {
+        init_maylib_headless();
+        let first = init_window("First Window", 100, 100)
+            .expect("Code in tests should be correct");
+        let second = init_window("Second Window", 100, 100)
+            .expect("Code in tests should be correct");
+        // Window ids from older code still switch
+        switch_window(second.id());
+        assert_eq!(get_current_window(), second);
+        switch_window(first);
+        let closed = Arc::new(AtomicBool::new(false));
+        let window_closed = closed.clone();
+        on_window_closed(move |window| {
+            if window == first {
+                window_closed.store(true, Ordering::SeqCst);
+            }
+        });
+
+        // A vetoed close request leaves the window open
+        first.on_close_requested(|_| false);
+        first.request_close();
+        assert!(!first.should_close());
+
+        // Closing the current window falls back to another open window
+        first.close();
+        assert!(closed.load(Ordering::SeqCst));
+        assert!(first.should_close());
+        assert_eq!(get_current_window(), second);
+
+        second.close();
+        assert!(window_should_close());
+        assert!(try_get_current_window().is_err());
+    }
+
+    #[test]
+    pub fn window_input_test() {
+        init_maylib_headless();
+        let window = init_window("Input Test", 100, 100)
+            .expect("Code in tests should be correct");
+        begin_drawing();
+        end_drawing();
+        // Nothing has been typed or clicked in this window
+        assert!(!window.key_pressed(Keycode::Delete));
+        assert!(!window.mouse_button_pressed(MouseButton::Left));
+        assert!(!window.is_file_dropped());
+        assert!(window.load_dropped_files().is_empty());
+        window.close();
+        assert!(window.try_mouse_position().is_err());
+    }
+
+    #[test]
+    pub fn mouse_edge_test() {
+        init_maylib_headless();
+        let window = init_window("Mouse Edge Test", 100, 100)
+            .expect("Code in tests should be correct");
+        let push_button = |down: bool| {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            let event = if down {
+                Event::MouseButtonDown {
+                    timestamp: 0,
+                    window_id: window.id(),
+                    which: 0,
+                    mouse_btn: sdl2::mouse::MouseButton::Left,
+                    clicks: 1,
+                    x: 10,
+                    y: 20,
+                }
+            } else {
+                Event::MouseButtonUp {
+                    timestamp: 0,
+                    window_id: window.id(),
+                    which: 0,
+                    mouse_btn: sdl2::mouse::MouseButton::Left,
+                    clicks: 1,
+                    x: 10,
+                    y: 20,
+                }
+            };
+            get.video
+                .sdl()
+                .event()
+                .and_then(|events| events.push_event(event))
+                .expect("Code in tests should be correct");
+        };
+
+        push_button(true);
+        begin_drawing();
+        end_drawing();
+        assert!(window.is_mouse_button_pressed(MouseButton::Left));
+        assert!(window.is_mouse_button_down(MouseButton::Left));
+        assert!(!window.is_mouse_button_up(MouseButton::Left));
+        assert_eq!(window.mouse_position(), (10, 20));
+
+        // Pressed only lasts the frame the button went down in
+        begin_drawing();
+        end_drawing();
+        assert!(!window.is_mouse_button_pressed(MouseButton::Left));
+        assert!(window.is_mouse_button_down(MouseButton::Left));
+
+        push_button(false);
+        begin_drawing();
+        end_drawing();
+        assert!(window.is_mouse_button_released(MouseButton::Left));
+        assert!(window.is_mouse_button_up(MouseButton::Left));
+
+        begin_drawing();
+        end_drawing();
+        assert!(!window.is_mouse_button_released(MouseButton::Left));
+        assert!(window.is_mouse_button_up(MouseButton::Left));
+
+        window.set_mouse_position(30, 40);
+        assert_eq!(window.mouse_position(), (30, 40));
+        window.close();
+    }
+
+    #[test]
+    pub fn key_edge_test() {
+        init_maylib_headless();
+        let window = init_window("Key Edge Test", 100, 100)
+            .expect("Code in tests should be correct");
+        let push_key = |down: bool, repeat: bool| {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            let (timestamp, window_id, keymod) = (0, window.id(), sdl2::keyboard::Mod::NOMOD);
+            let (keycode, scancode) = (Some(sdl2::keyboard::Keycode::A), Some(sdl2::keyboard::Scancode::A));
+            let event = if down {
+                Event::KeyDown { timestamp, window_id, keycode, scancode, keymod, repeat }
+            } else {
+                Event::KeyUp { timestamp, window_id, keycode, scancode, keymod, repeat }
+            };
+            get.video
+                .sdl()
+                .event()
+                .and_then(|events| events.push_event(event))
+                .expect("Code in tests should be correct");
+        };
+
+        push_key(true, false);
+        begin_drawing();
+        end_drawing();
+        assert!(window.is_key_pressed(Scancode::A));
+        assert!(!window.is_key_pressed_repeat(Scancode::A));
+        assert!(window.is_key_down(Scancode::A));
+
+        // Held keys repeat without being pressed again
+        push_key(true, true);
+        begin_drawing();
+        end_drawing();
+        assert!(!window.is_key_pressed(Scancode::A));
+        assert!(window.is_key_pressed_repeat(Scancode::A));
+        assert!(window.is_key_down(Scancode::A));
+
+        begin_drawing();
+        end_drawing();
+        assert!(!window.is_key_pressed_repeat(Scancode::A));
+
+        push_key(false, false);
+        begin_drawing();
+        end_drawing();
+        assert!(window.is_key_released(Scancode::A));
+        assert!(window.is_key_up(Scancode::A));
+
+        begin_drawing();
+        end_drawing();
+        assert!(!window.is_key_released(Scancode::A));
+        window.close();
+    }
+
+    #[test]
+    pub fn key_name_test() {
+        init_maylib_headless();
+        assert_eq!(Scancode::LShift.name(), "Left Shift");
+        assert_eq!(Scancode::from_name("Left Shift"), Some(Scancode::LShift));
+        assert_eq!(Keycode::Z.name(), "Z");
+        assert_eq!(Keycode::from_name("Z"), Some(Keycode::Z));
+        assert_eq!(Scancode::Unknown.name(), "");
+        assert_eq!(Keycode::from_name("Not A Key"), None);
+    }
+
+    #[test]
+    pub fn text_input_test() {
+        init_maylib_headless();
+        let window = init_window("Text Input Test", 100, 100)
+            .expect("Code in tests should be correct");
+        start_text_input(window);
+        assert!(is_text_input_active());
+        assert_eq!(window.text_composition(), None);
+        window.close();
+        stop_text_input();
+        assert!(!is_text_input_active());
+    }
+
+    #[test]
+    pub fn cursor_test() {
+        init_maylib_headless();
+        hide_cursor();
+        assert!(cursor_hidden());
+        show_cursor();
+        assert!(!cursor_hidden());
+
+        let image = load_image_bytes(include_bytes!(".././maylib.png"));
+        assert!(image.width() > 0 && image.height() > 0);
+        assert_eq!(image.pixels().len(), (image.width() * image.height() * 4) as usize);
+    }
+
+    #[test]
+    pub fn virtual_gamepad_test() {
+        init_maylib_headless();
+        let gamepad = attach_virtual_gamepad();
+        assert!(is_gamepad_available(gamepad));
+        set_virtual_gamepad_button(gamepad, GamepadButton::A, true);
+        set_virtual_gamepad_axis(gamepad, GamepadAxis::LeftX, 1.0);
+        begin_drawing();
+        end_drawing();
+        assert!(is_gamepad_button_down(gamepad, GamepadButton::A));
+        assert!(is_gamepad_button_up(gamepad, GamepadButton::B));
+        assert!(get_gamepad_axis_movement(gamepad, GamepadAxis::LeftX) > 0.99);
+        assert_eq!(get_gamepad_axis_movement(gamepad, GamepadAxis::LeftY), 0.0);
+        detach_virtual_gamepad(gamepad);
+        assert!(!is_gamepad_available(gamepad));
+    }
+
+    #[test]
+    pub fn gesture_test() {
+        let finger = |x: f32, y: f32| TouchPoint { id: 1, x, y };
+        let mut gestures = GestureState::default();
+
+        // Two quick taps in the same place
+        gestures.finger_down(&[finger(50.0, 50.0)], 0.0);
+        gestures.finger_up(&[], finger(50.0, 50.0), 0.1);
+        assert_eq!(gestures.current, Gesture::Tap);
+        gestures.begin_frame(false, 0.15);
+        assert_eq!(gestures.current, Gesture::None);
+        gestures.finger_down(&[finger(52.0, 50.0)], 0.2);
+        gestures.finger_up(&[], finger(52.0, 50.0), 0.25);
+        assert_eq!(gestures.current, Gesture::DoubleTap);
+
+        // A finger held still
+        gestures.finger_down(&[finger(50.0, 50.0)], 1.0);
+        gestures.begin_frame(true, 1.6);
+        assert_eq!(gestures.current, Gesture::Hold);
+        gestures.finger_up(&[], finger(50.0, 50.0), 1.7);
+
+        // A fast drag to the left
+        gestures.finger_down(&[finger(200.0, 50.0)], 2.0);
+        gestures.finger_moved(&[finger(100.0, 50.0)]);
+        assert_eq!(gestures.current, Gesture::Drag);
+        assert_eq!(gestures.drag_vector, (-100.0, 0.0));
+        gestures.finger_up(&[], finger(100.0, 50.0), 2.1);
+        assert_eq!(gestures.current, Gesture::SwipeLeft);
+    }
+
+    #[test]
+    pub fn recording_test() {
+        init_maylib_headless();
+        let window = init_window("Recording Test", 100, 100)
+            .expect("Code in tests should be correct");
+        let path = std::env::temp_dir().join("maylib-recording-test.rec");
+        let path = path.to_str().expect("Code in tests should be correct");
+        start_recording(path);
+        assert!(is_recording());
+        for _ in 0..3 {
+            begin_drawing();
+            end_drawing();
+        }
+        stop_recording();
+        assert!(!is_recording());
+
+        // Each begin_drawing replays a frame, and the one after the last brings live input back
+        start_playback(path);
+        assert!(is_playing_back());
+        let mut frames = 0;
+        while is_playing_back() && frames < 100 {
+            begin_drawing();
+            end_drawing();
+            frames += 1;
+        }
+        assert_eq!(frames, 4);
+        window.close();
+        assert!(try_start_playback("./maylib.png").is_err());
+    }
+
+    #[test]
+    pub fn action_bindings_test() {
+        init_maylib_headless();
+        assert_eq!(InputBinding::from_name("key:Space"), Some(InputBinding::Key(Keycode::Space)));
+        assert_eq!(InputBinding::Gamepad(GamepadButton::A).name(), "gamepad:a");
+        assert_eq!(InputBinding::from_name("mouse:wheel"), None);
+
+        bind_action("test jump", InputBinding::Key(Keycode::Space));
+        bind_action("test jump", InputBinding::Gamepad(GamepadButton::A));
+        bind_axis_keys("test_move", InputBinding::Key(Keycode::A), InputBinding::Key(Keycode::D));
+        // Binding the same keys again doesn't add them twice
+        bind_axis_keys("test_move", InputBinding::Key(Keycode::A), InputBinding::Key(Keycode::D));
+        bind_axis_gamepad("test_move", GamepadAxis::LeftX);
+        let path = std::env::temp_dir().join("maylib-bindings-test.bindings");
+        let path = path.to_str().expect("Code in tests should be correct");
+        save_bindings(path);
+        let saved = std::fs::read_to_string(path).expect("Code in tests should be correct");
+        assert!(saved.contains("negative = [\"key:A\"]\n"));
+        clear_action("test jump");
+        clear_axis("test_move");
+        assert!(try_is_action_pressed("test jump").is_err());
+
+        // Loading brings back exactly what was saved
+        load_bindings(path);
+        assert_eq!(
+            get_action_bindings("test jump"),
+            vec![InputBinding::Key(Keycode::Space), InputBinding::Gamepad(GamepadButton::A)]
+        );
+        let window = init_window("Action Test", 100, 100)
+            .expect("Code in tests should be correct");
+        switch_window(window);
+        assert!(!is_action_pressed("test jump"));
+        assert_eq!(get_axis_value("test_move"), 0.0);
+
+        // Waiting for the next press, like a rebinding menu would
+        listen_for_input();
+        assert!(is_listening_for_input());
+        {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            get.video
+                .sdl()
+                .event()
+                .and_then(|events| {
+                    events.push_event(Event::MouseButtonDown {
+                        timestamp: 0,
+                        window_id: window.id(),
+                        which: 0,
+                        mouse_btn: sdl2::mouse::MouseButton::Right,
+                        clicks: 1,
+                        x: 0,
+                        y: 0,
+                    })
+                })
+                .expect("Code in tests should be correct");
+        }
+        begin_drawing();
+        end_drawing();
+        assert!(!is_listening_for_input());
+        assert_eq!(get_listened_input(), Some(InputBinding::Mouse(MouseButton::Right)));
+        window.close();
+    }
+
+    #[test]
+    pub fn event_convert_test() {
+        init_maylib_headless();
+        let window = init_window("Event Test", 100, 100)
+            .expect("Code in tests should be correct");
+        let click = |window_id| Event::MouseButtonDown {
+            timestamp: 0,
+            window_id,
+            which: 0,
+            mouse_btn: sdl2::mouse::MouseButton::Left,
+            clicks: 2,
+            x: 5,
+            y: 6,
+        };
+        {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            assert_eq!(
+                convert(&get, &click(window.id()), None, None),
+                Some(crate::Event::MouseButtonDown { window, button: MouseButton::Left, x: 5, y: 6, clicks: 2 })
+            );
+            assert_eq!(convert(&get, &Event::Quit { timestamp: 0 }, None, None), Some(crate::Event::Quit));
+        }
+
+        // Waiting hands back the event as soon as it arrives
+        begin_drawing();
+        end_drawing();
+        poll_events().for_each(drop);
+        {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            get.video
+                .sdl()
+                .event()
+                .and_then(|events| events.push_event(click(window.id())))
+                .expect("Code in tests should be correct");
+        }
+        assert_eq!(
+            wait_event_timeout(1.0),
+            Some(crate::Event::MouseButtonDown { window, button: MouseButton::Left, x: 5, y: 6, clicks: 2 })
+        );
+        assert!(window.is_mouse_button_down(MouseButton::Left));
+
+        window.close();
+        // Events for a closed window are dropped
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        assert_eq!(convert(&get, &click(window.id()), None, None), None);
+    }
+
+    #[test]
+    pub fn clipboard_test() {
+        init_maylib_headless();
+        set_clipboard_text("Hello Maylib");
+        assert!(has_clipboard_text());
+        assert_eq!(get_clipboard_text(), Some("Hello Maylib".to_string()));
+        // An empty clipboard reads as no text instead of panicking
+        set_clipboard_text("");
+        assert!(!has_clipboard_text());
+        assert_eq!(get_clipboard_text(), None);
+
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let update = Event::ClipboardUpdate { timestamp: 0 };
+        assert_eq!(convert(&get, &update, None, None), Some(crate::Event::ClipboardUpdated));
+    }
+
+    #[test]
+    pub fn random_seed_test() {
+        let draw = || {
+            let numbers: Vec<i32> = (0..10).map(|_| get_random_i32(0, 100)).collect();
+            (numbers, get_random_normal(0.0, 1.0), get_perlin_noise(1.5, 2.25))
+        };
+        set_random_seed(42);
+        let first = draw();
+        set_random_seed(42);
+        assert_eq!(draw(), first);
+        assert_eq!(get_random_seed(), 42);
+
+        // Ranges include both ends, so one number is fine
+        assert_eq!(get_random_i32(7, 7), 7);
+        assert_eq!(get_random_f64(1.5, 1.5), 1.5);
+        assert!(try_get_random_f64(0.0, f64::NAN).is_err());
+        assert!(try_get_random_f64(f64::NEG_INFINITY, 1.0).is_err());
+        assert!(try_get_random_f64(-f64::MAX, f64::MAX).is_err());
+        let mut sequence = load_random_sequence(10, 1, 10);
+        sequence.sort();
+        assert_eq!(sequence, (1..=10).collect::<Vec<_>>());
+        assert_eq!(load_random_sequence(5, 3, 4).len(), 2);
+
+        assert_eq!(choose_random::<i32>(&[]), None);
+        assert_eq!(choose_random_weighted(&[("never", 0.0), ("always", 1.0)]), Some(&"always"));
+        assert_eq!(choose_random_weighted(&[("never", 0.0)]), None);
+        let (x, y) = get_random_unit_vector();
+        assert!(((x * x + y * y) - 1.0).abs() < 1e-5);
+        assert_eq!(get_perlin_noise(3.0, 4.0), 0.0);
+    }
+
+    #[test]
+    pub fn vsync_after_open_test() {
+        init_maylib_headless();
+        let window = init_window("Vsync Test", 100, 100)
+            .expect("Code in tests should be correct");
+        // Setting vsync after the window exists still limits the frame rate
+        set_frame_limit(FrameLimit::Vsync);
+        let start = std::time::Instant::now();
+        for _ in 0..4 {
+            begin_drawing();
+            window.clear_background(Color::Black);
+            end_drawing();
+        }
+        set_frame_limit(FrameLimit::Unlimited);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(30));
+        window.close();
+    }
+
+    #[test]
+    pub fn finger_owner_test() {
+        init_maylib_headless();
+        let first = init_window("Finger Test 1", 100, 100)
+            .expect("Code in tests should be correct");
+        let second = init_window("Finger Test 2", 100, 100)
+            .expect("Code in tests should be correct");
+        let finger = |down: bool| {
+            let (timestamp, touch_id, finger_id, x, y, dx, dy, pressure) = (0, 1, 7, 0.5, 0.5, 0.0, 0.0, 1.0);
+            if down {
+                Event::FingerDown { timestamp, touch_id, finger_id, x, y, dx, dy, pressure }
+            } else {
+                Event::FingerUp { timestamp, touch_id, finger_id, x, y, dx, dy, pressure }
+            }
+        };
+        let focus = |window: crate::types::WindowHandle| {
+            let mut get = MAYLIB.lock().expect("Should be able to lock");
+            for (id, other) in get.windows.iter_mut() {
+                other.focused = *id == window.id();
+            }
+        };
+        let push = |event: Event| {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            get.video
+                .sdl()
+                .event()
+                .and_then(|events| events.push_event(event))
+                .expect("Code in tests should be correct");
+        };
+        focus(first);
+        push(finger(true));
+        begin_drawing();
+        end_drawing();
+        assert_eq!(first.touch_point_count(), 1);
+
+        // The finger stays with the window it went down in after focus moves
+        focus(second);
+        push(finger(false));
+        begin_drawing();
+        end_drawing();
+        assert_eq!(first.touch_point_count(), 0);
+        assert_eq!(second.touch_point_count(), 0);
+        assert!(poll_events().any(|event| matches!(event, crate::Event::TouchUp { window, .. } if window == first)));
+        first.close();
+        second.close();
+    }
+
+    #[test]
+    pub fn gamepad_hotplug_test() {
+        init_maylib_headless();
+        let connected = Arc::new(Mutex::new(Vec::new()));
+        let disconnected = Arc::new(Mutex::new(Vec::new()));
+        let on_connect = connected.clone();
+        on_gamepad_connected(move |gamepad| on_connect.lock().expect("Should be able to lock").push(gamepad));
+        let on_disconnect = disconnected.clone();
+        on_gamepad_disconnected(move |gamepad| on_disconnect.lock().expect("Should be able to lock").push(gamepad));
+
+        // Virtual gamepads are announced like real ones, at the next begin_drawing
+        let first = attach_virtual_gamepad();
+        let second = attach_virtual_gamepad();
+        begin_drawing();
+        end_drawing();
+        assert_eq!(*connected.lock().expect("Should be able to lock"), vec![first, second]);
+        assert!(poll_events().any(|event| event == crate::Event::GamepadConnected { gamepad: second }));
+
+        // Detaching the first shifts SDL's device indexes, and the second still detaches
+        detach_virtual_gamepad(first);
+        detach_virtual_gamepad(second);
+        begin_drawing();
+        end_drawing();
+        assert_eq!(*disconnected.lock().expect("Should be able to lock"), vec![first, second]);
+        assert!(poll_events().any(|event| event == crate::Event::GamepadDisconnected { gamepad: first }));
+        assert!(!is_gamepad_available(second));
+    }
+
+    #[test]
+    pub fn wait_event_unlocked_test() {
+        init_maylib_headless();
+        begin_drawing();
+        end_drawing();
+        poll_events().for_each(drop);
+        // Other threads can use maylib while the main thread waits
+        let start = std::time::Instant::now();
+        let other = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            is_recording();
+            start.elapsed()
+        });
+        wait_event_timeout(0.5);
+        // Holding maylib for the whole wait would keep the other thread out for half a second
+        let other_took = other.join().expect("Code in tests should be correct");
+        assert!(other_took < std::time::Duration::from_millis(250));
+    }
+
+    #[test]
+    pub fn borderless_toggle_test() {
+        init_maylib_headless();
+        let window = WindowBuilder::new("Borderless Test", 100, 100)
+            .borderless()
+            .build()
+            .expect("Code in tests should be correct");
+        let bordered = || {
+            let get = MAYLIB.lock().expect("Should be able to lock");
+            get.window(window).map(|window| window.bordered).expect("Code in tests should be correct")
+        };
+        assert!(!bordered());
+        // One toggle from borderless gives the window its border back
+        window.toggle_borderless_windowed();
+        assert!(bordered());
+        window.toggle_borderless_windowed();
+        assert!(!bordered());
+        window.close();
     }
 }
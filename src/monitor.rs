@@ -0,0 +1,130 @@
+use crate::core::{get_current_window, try_get_current_window, MAYLIB};
+use crate::error::Error;
+use crate::types::{DisplayMode, Rectangle, WindowHandle};
+use sdl2::video;
+
+pub fn get_monitor_count() -> i32 {
+    try_get_monitor_count().expect("Should be able to count monitors")
+}
+
+pub fn try_get_monitor_count() -> Result<i32, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.video.num_video_displays().map_err(Error::Sdl)
+}
+
+pub fn get_monitor_name(monitor: i32) -> String {
+    try_get_monitor_name(monitor).expect("Monitor should exist")
+}
+
+pub fn try_get_monitor_name(monitor: i32) -> Result<String, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.video.display_name(monitor).map_err(Error::Sdl)
+}
+
+/// get the area a monitor covers on the desktop
+pub fn get_monitor_bounds(monitor: i32) -> Rectangle {
+    try_get_monitor_bounds(monitor).expect("Monitor should exist")
+}
+
+pub fn try_get_monitor_bounds(monitor: i32) -> Result<Rectangle, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    Ok(get.video.display_bounds(monitor).map_err(Error::Sdl)?.into())
+}
+
+/// get the area of a monitor not covered by taskbars, docks and menu bars
+pub fn get_monitor_usable_bounds(monitor: i32) -> Rectangle {
+    try_get_monitor_usable_bounds(monitor).expect("Monitor should exist")
+}
+
+pub fn try_get_monitor_usable_bounds(monitor: i32) -> Result<Rectangle, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    Ok(get.video.display_usable_bounds(monitor).map_err(Error::Sdl)?.into())
+}
+
+/// get the horizontal and vertical DPI of a monitor
+pub fn get_monitor_dpi(monitor: i32) -> (f32, f32) {
+    try_get_monitor_dpi(monitor).expect("Monitor should exist")
+}
+
+pub fn try_get_monitor_dpi(monitor: i32) -> Result<(f32, f32), Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let (_, horizontal, vertical) = get.video.display_dpi(monitor).map_err(Error::Sdl)?;
+    Ok((horizontal, vertical))
+}
+
+/// get the refresh rate of a monitor's current display mode
+pub fn get_monitor_refresh_rate(monitor: i32) -> i32 {
+    try_get_monitor_refresh_rate(monitor).expect("Monitor should exist")
+}
+
+pub fn try_get_monitor_refresh_rate(monitor: i32) -> Result<i32, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    Ok(get
+        .video
+        .current_display_mode(monitor)
+        .map_err(Error::Sdl)?
+        .refresh_rate)
+}
+
+/// get every display mode a monitor supports
+pub fn get_monitor_display_modes(monitor: i32) -> Vec<DisplayMode> {
+    try_get_monitor_display_modes(monitor).expect("Monitor should exist")
+}
+
+pub fn try_get_monitor_display_modes(monitor: i32) -> Result<Vec<DisplayMode>, Error> {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    let count = get.video.num_display_modes(monitor).map_err(Error::Sdl)?;
+    let mut modes = Vec::with_capacity(count.max(0) as usize);
+    for mode in 0..count {
+        let mode = get.video.display_mode(monitor, mode).map_err(Error::Sdl)?;
+        modes.push(mode.into());
+    }
+    Ok(modes)
+}
+
+/// get the monitor the current window is on
+pub fn get_current_monitor() -> i32 {
+    get_current_window().monitor()
+}
+
+pub fn try_get_current_monitor() -> Result<i32, Error> {
+    try_get_current_window()?.try_monitor()
+}
+
+/// move the current window to the center of a monitor
+pub fn move_window_to_monitor(monitor: i32) {
+    get_current_window().move_to_monitor(monitor);
+}
+
+pub fn try_move_window_to_monitor(monitor: i32) -> Result<(), Error> {
+    try_get_current_window()?.try_move_to_monitor(monitor)
+}
+
+impl WindowHandle {
+    /// get the monitor this window is on
+    pub fn monitor(self) -> i32 {
+        self.try_monitor().expect("Window handle should be valid")
+    }
+
+    pub fn try_monitor(self) -> Result<i32, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        get.window(self)?.window.display_index().map_err(Error::Sdl)
+    }
+
+    /// move this window to the center of a monitor
+    pub fn move_to_monitor(self, monitor: i32) {
+        self.try_move_to_monitor(monitor)
+            .expect("Monitor should exist")
+    }
+
+    pub fn try_move_to_monitor(self, monitor: i32) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let bounds = get.video.display_usable_bounds(monitor).map_err(Error::Sdl)?;
+        let window = &mut get.window_mut(self)?.window;
+        let (width, height) = window.size();
+        let x = bounds.x() + (bounds.width() as i32 - width as i32) / 2;
+        let y = bounds.y() + (bounds.height() as i32 - height as i32) / 2;
+        window.set_position(video::WindowPos::Positioned(x), video::WindowPos::Positioned(y));
+        Ok(())
+    }
+}
@@ -0,0 +1,174 @@
+use crate::error::Error;
+use lazy_static::lazy_static;
+use rand::distr::Uniform;
+use rand::distr::weighted::WeightedIndex;
+use rand::rngs::StdRng;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, SeedableRng};
+use std::f64::consts::TAU;
+use std::sync::Mutex;
+
+/// Every random number comes from here, so one seed reproduces everything
+struct Random {
+    seed: u64,
+    rng: StdRng,
+    /// Shuffled 0 to 255, for `get_perlin_noise`
+    permutation: [u8; 256],
+}
+
+impl Random {
+    fn from_seed(seed: u64) -> Self {
+        // Noise gets its own generator so drawing numbers doesn't change the noise
+        let mut permutation = [0; 256];
+        for (i, value) in permutation.iter_mut().enumerate() {
+            *value = i as u8;
+        }
+        permutation.shuffle(&mut StdRng::seed_from_u64(seed));
+        Random {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            permutation,
+        }
+    }
+}
+
+lazy_static! {
+    static ref RANDOM: Mutex<Random> = Mutex::new(Random::from_seed(rand::random()));
+}
+
+fn with_rng<T>(f: impl FnOnce(&mut StdRng) -> T) -> T {
+    let mut random = RANDOM.lock().expect("Should be able to lock");
+    f(&mut random.rng)
+}
+
+/// restart random numbers and noise from a seed, so the same seed always gives the same results.
+/// Without this the seed is picked randomly at startup
+pub fn set_random_seed(seed: u64) {
+    let mut random = RANDOM.lock().expect("Should be able to lock");
+    *random = Random::from_seed(seed);
+}
+
+/// get the last seed given to `set_random_seed`, or the one picked at startup. Log it to reproduce a run later
+pub fn get_random_seed() -> u64 {
+    let random = RANDOM.lock().expect("Should be able to lock");
+    random.seed
+}
+
+/// get a random number from `min` to `max`, including both
+pub fn get_random_i32(min: i32, max: i32) -> i32 {
+    with_rng(|rng| rng.random_range(min.min(max)..=min.max(max)))
+}
+
+/// get a random number from `min` to `max`, including both
+pub fn get_random_i64(min: i64, max: i64) -> i64 {
+    with_rng(|rng| rng.random_range(min.min(max)..=min.max(max)))
+}
+
+/// get a random number from `min` to `max`, including both.
+/// Panics if either is NaN or infinite, or they are too far apart, see `try_get_random_f64`
+pub fn get_random_f64(min: f64, max: f64) -> f64 {
+    try_get_random_f64(min, max).expect("Can't get random number")
+}
+
+pub fn try_get_random_f64(min: f64, max: f64) -> Result<f64, Error> {
+    // `f64::min` skips NaN, so check the bounds before ordering them
+    if !min.is_finite() || !max.is_finite() {
+        return Err(Error::InvalidRange(min, max));
+    }
+    let range = Uniform::new_inclusive(min.min(max), min.max(max)).map_err(|_| Error::InvalidRange(min, max))?;
+    Ok(with_rng(|rng| rng.sample(range)))
+}
+
+/// flip a coin
+pub fn get_random_bool() -> bool {
+    with_rng(|rng| rng.random_bool(0.5))
+}
+
+/// get true with this chance, from 0 for never to 1 for always
+pub fn get_random_chance(probability: f64) -> bool {
+    with_rng(|rng| rng.random_bool(probability.clamp(0.0, 1.0)))
+}
+
+/// get a number from a bell curve around `mean`. About two thirds land within `std_dev` of it
+pub fn get_random_normal(mean: f64, std_dev: f64) -> f64 {
+    // Box-Muller, with the first number kept above 0 so its log is finite
+    let (u1, u2): (f64, f64) = with_rng(|rng| (1.0 - rng.random::<f64>(), rng.random()));
+    mean + std_dev * (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+}
+
+/// get a direction with length 1, pointing anywhere evenly
+pub fn get_random_unit_vector() -> (f32, f32) {
+    let angle = with_rng(|rng| rng.random_range(0.0..TAU));
+    (angle.cos() as f32, angle.sin() as f32)
+}
+
+/// pick one item, or `None` if there are none
+pub fn choose_random<T>(items: &[T]) -> Option<&T> {
+    with_rng(|rng| items.choose(rng))
+}
+
+/// pick one item, where an item with weight 2 is picked twice as often as one with weight 1.
+/// `None` if there are no items or no weight above 0
+pub fn choose_random_weighted<T>(items: &[(T, f64)]) -> Option<&T> {
+    let weights = WeightedIndex::new(items.iter().map(|(_, weight)| weight.max(0.0))).ok()?;
+    let index = with_rng(|rng| rng.sample(weights));
+    Some(&items[index].0)
+}
+
+/// put items in a random order
+pub fn shuffle<T>(items: &mut [T]) {
+    with_rng(|rng| items.shuffle(rng));
+}
+
+/// get `count` different random numbers from `min` to `max`, including both.
+/// There can't be more numbers than fit in the range, so `count` is capped to that
+pub fn load_random_sequence(count: usize, min: i32, max: i32) -> Vec<i32> {
+    let (min, max) = (min.min(max), min.max(max));
+    let length = (max as i64 - min as i64 + 1) as usize;
+    let indices = with_rng(|rng| rand::seq::index::sample(rng, length, count.min(length)));
+    indices
+        .into_iter()
+        .map(|index| (min as i64 + index as i64) as i32)
+        .collect()
+}
+
+/// get smooth noise from -1 to 1 that changes gradually as `x` and `y` move, for terrain and clouds.
+/// The same seed always gives the same noise. Whole numbers are always 0, so scale positions down first
+pub fn get_perlin_noise(x: f32, y: f32) -> f32 {
+    let random = RANDOM.lock().expect("Should be able to lock");
+    let hash = |i: i32| random.permutation[(i & 255) as usize] as i32;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (xi, yi) = (x0 as i32, y0 as i32);
+    let (xf, yf) = (x - x0, y - y0);
+    let (u, v) = (fade(xf), fade(yf));
+    let corner = |dx: i32, dy: i32| {
+        let hash = hash(hash(xi + dx) + yi + dy);
+        gradient(hash, xf - dx as f32, yf - dy as f32)
+    };
+    let top = lerp(corner(0, 0), corner(1, 0), u);
+    let bottom = lerp(corner(0, 1), corner(1, 1), u);
+    lerp(top, bottom, v).clamp(-1.0, 1.0)
+}
+
+/// Ease between grid points so the noise has no visible creases
+fn fade(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// How much a grid point's gradient pushes the noise at an offset from it
+fn gradient(hash: i32, x: f32, y: f32) -> f32 {
+    match hash & 7 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x,
+        5 => -x,
+        6 => y,
+        _ => -y,
+    }
+}
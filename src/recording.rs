@@ -0,0 +1,573 @@
+use crate::core::MAYLIB;
+use crate::error::Error;
+use crate::gamepad::{self, GamepadHotplug};
+use crate::types::{Maylib, Playback, Recording};
+use sdl2::controller::{Axis, Button};
+use sdl2::event::{Event, WindowEvent};
+use sdl2::keyboard::{Keycode, Mod, Scancode};
+use sdl2::mouse::{MouseButton, MouseState, MouseWheelDirection};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+/// Starts every recording, with the format version in the last byte
+const MAGIC: &[u8; 8] = b"MAYREC\0\x01";
+
+/// start writing every frame's input and frame time to a file, until `stop_recording`.
+/// The file is written as frames happen, so it survives a crash. Gamepads already connected are recorded as connecting first
+pub fn start_recording(path: &str) {
+    try_start_recording(path).expect("Can't start recording");
+}
+
+pub fn try_start_recording(path: &str) -> Result<(), Error> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(MAGIC)?;
+    writer.flush()?;
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    let last_time = get.time();
+    get.recording = Some(Recording {
+        writer,
+        last_time,
+        error: None,
+        pending: Vec::new(),
+    });
+    for slot in 0..get.gamepads.len() {
+        if get.gamepads[slot].is_some() {
+            record_gamepad(&mut get, slot);
+        }
+    }
+    Ok(())
+}
+
+/// stop recording and finish writing the file
+pub fn stop_recording() {
+    try_stop_recording().expect("Can't finish recording");
+}
+
+pub fn try_stop_recording() -> Result<(), Error> {
+    let recording = {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.recording.take()
+    };
+    let Some(mut recording) = recording else {
+        return Ok(());
+    };
+    // A write that failed during `begin_drawing` is reported here
+    if let Some(error) = recording.error {
+        return Err(error.into());
+    }
+    recording.writer.flush()?;
+    Ok(())
+}
+
+pub fn is_recording() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.recording.is_some()
+}
+
+/// replay a recording instead of live input, one recorded frame per `begin_drawing`.
+/// Time follows the recorded frame times and frames aren't paced, so playback runs as fast as it can
+/// and gives the same results every time. Recorded gamepads are played on virtual ones in the slots they were recorded in,
+/// and live gamepads are put away until playback ends. Live input comes back when the recording runs out
+pub fn start_playback(path: &str) {
+    try_start_playback(path).expect("Can't start playback");
+}
+
+pub fn try_start_playback(path: &str) -> Result<(), Error> {
+    let bytes = std::fs::read(path)?;
+    let frames = decode_frames(&bytes)?;
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    end_playback(&mut get);
+    let time = get.time();
+    let live_gamepads = std::mem::take(&mut get.gamepads);
+    get.playback = Some(Playback {
+        frames,
+        time,
+        live_gamepads,
+        slots: HashMap::new(),
+    });
+    Ok(())
+}
+
+/// stop replaying and go back to live input
+pub fn stop_playback() {
+    let mut get = MAYLIB.lock().expect("Should be able to lock");
+    end_playback(&mut get);
+}
+
+/// unplug the gamepads standing in for recorded ones and put the live ones back
+fn end_playback(get: &mut Maylib) {
+    let Some(playback) = get.playback.take() else {
+        return;
+    };
+    let replayed = std::mem::replace(&mut get.gamepads, playback.live_gamepads);
+    for gamepad in replayed.into_iter().flatten().filter(|gamepad| gamepad.is_virtual) {
+        // The gamepad is already gone from maylib, so there is nothing to do if SDL fails
+        let _ = gamepad::detach_virtual_joystick(gamepad.controller.instance_id());
+    }
+}
+
+/// check if a recording is being replayed
+pub fn is_playing_back() -> bool {
+    let get = MAYLIB.lock().expect("Should be able to lock");
+    get.playback.is_some()
+}
+
+/// take the next recorded frame's events and move the playback clock forward, called by `begin_drawing`
+pub(crate) fn next_playback_frame(get: &mut Maylib) -> Option<Vec<Event>> {
+    let playback = get.playback.as_mut()?;
+    match playback.frames.pop_front() {
+        Some((delta, events)) => {
+            playback.time += delta;
+            Some(events)
+        }
+        None => {
+            end_playback(get);
+            None
+        }
+    }
+}
+
+/// swap the slots in a recorded gamepad event for the gamepads playing them, called by `begin_drawing`.
+/// A recorded gamepad connecting attaches a virtual one to stand in for it
+pub(crate) fn replay_event(get: &mut Maylib, event: Event) -> Option<Event> {
+    if get.playback.is_none() {
+        return Some(event);
+    }
+    let instance_id = |get: &Maylib, slot: u32| {
+        get.gamepads
+            .get(slot as usize)?
+            .as_ref()
+            .map(|gamepad| gamepad.controller.instance_id())
+    };
+    Some(match event {
+        Event::ControllerDeviceAdded { timestamp, which: slot } => {
+            let index = gamepad::attach_virtual_joystick().ok()?;
+            get.playback.as_mut()?.slots.insert(index as u32, slot as usize);
+            Event::ControllerDeviceAdded { timestamp, which: index as u32 }
+        }
+        Event::ControllerDeviceRemoved { timestamp, which: slot } => {
+            let which = instance_id(get, slot)?;
+            // Handling the event drops the gamepad, but SDL keeps the joystick until it is detached
+            let _ = gamepad::detach_virtual_joystick(which);
+            Event::ControllerDeviceRemoved { timestamp, which }
+        }
+        Event::ControllerButtonDown { timestamp, which: slot, button } => Event::ControllerButtonDown {
+            timestamp,
+            which: instance_id(get, slot)?,
+            button,
+        },
+        Event::ControllerButtonUp { timestamp, which: slot, button } => Event::ControllerButtonUp {
+            timestamp,
+            which: instance_id(get, slot)?,
+            button,
+        },
+        Event::ControllerAxisMotion { timestamp, which: slot, axis, value } => Event::ControllerAxisMotion {
+            timestamp,
+            which: instance_id(get, slot)?,
+            axis,
+            value,
+        },
+        event => event,
+    })
+}
+
+/// encode an event for the next recorded frame, called by `begin_drawing` and `wait_event_timeout` after handling it
+pub(crate) fn record_event(get: &mut Maylib, event: &Event, hotplug: Option<&GamepadHotplug>) {
+    if get.recording.is_none() {
+        return;
+    }
+    let encoded = match hotplug {
+        Some(&GamepadHotplug::Connected(slot)) => Some(Encoder::new(GAMEPAD_ADDED, 0).u32(slot as u32).0),
+        Some(&GamepadHotplug::Disconnected(slot)) => Some(Encoder::new(GAMEPAD_REMOVED, 0).u32(slot as u32).0),
+        None => encode_event(get, event),
+    };
+    if let (Some(recording), Some(encoded)) = (get.recording.as_mut(), encoded) {
+        recording.pending.push(encoded);
+    }
+}
+
+/// record a gamepad slot as it is now, for gamepads that connect or disconnect without an event
+pub(crate) fn record_gamepad(get: &mut Maylib, slot: usize) {
+    let Some(recording) = get.recording.as_mut() else {
+        return;
+    };
+    let Some(gamepad) = get.gamepads.get(slot).and_then(Option::as_ref) else {
+        recording.pending.push(Encoder::new(GAMEPAD_REMOVED, 0).u32(slot as u32).0);
+        return;
+    };
+    recording.pending.push(Encoder::new(GAMEPAD_ADDED, 0).u32(slot as u32).0);
+    // Held buttons and moved sticks would otherwise only show up once they change
+    for &button in &gamepad.buttons_down {
+        let button = Button::from(button).string();
+        recording.pending.push(Encoder::new(GAMEPAD_BUTTON_DOWN, 0).u32(slot as u32).str(&button).0);
+    }
+    for (&axis, &value) in &gamepad.axes {
+        let axis = Axis::from(axis).string();
+        let value = (value * i16::MAX as f32) as i16;
+        recording.pending.push(Encoder::new(GAMEPAD_AXIS, 0).u32(slot as u32).str(&axis).i32(value as i32).0);
+    }
+}
+
+/// write one frame to the recording, called by `begin_drawing` after handling its events
+pub(crate) fn record_frame(get: &mut Maylib, time: f64) {
+    let Some(recording) = get.recording.as_mut() else {
+        return;
+    };
+    let encoded = std::mem::take(&mut recording.pending);
+    if recording.error.is_some() {
+        return;
+    }
+    let mut frame = Vec::new();
+    frame.extend((time - recording.last_time).to_le_bytes());
+    frame.extend((encoded.len() as u32).to_le_bytes());
+    for event in encoded {
+        frame.extend(event);
+    }
+    recording.last_time = time;
+    if let Err(error) = recording.writer.write_all(&frame).and_then(|_| recording.writer.flush()) {
+        recording.error = Some(error);
+    }
+}
+
+// Event tags in the file. Never reuse or renumber these, or old recordings stop loading
+const KEY_DOWN: u8 = 1;
+const KEY_UP: u8 = 2;
+const TEXT_INPUT: u8 = 3;
+const TEXT_EDITING: u8 = 4;
+const MOUSE_MOTION: u8 = 5;
+const MOUSE_BUTTON_DOWN: u8 = 6;
+const MOUSE_BUTTON_UP: u8 = 7;
+const MOUSE_WHEEL: u8 = 8;
+const WINDOW: u8 = 9;
+const QUIT: u8 = 10;
+const DROP_BEGIN: u8 = 11;
+const DROP_FILE: u8 = 12;
+const DROP_TEXT: u8 = 13;
+const DROP_COMPLETE: u8 = 14;
+const FINGER_DOWN: u8 = 15;
+const FINGER_UP: u8 = 16;
+const FINGER_MOTION: u8 = 17;
+const GAMEPAD_ADDED: u8 = 18;
+const GAMEPAD_REMOVED: u8 = 19;
+const GAMEPAD_BUTTON_DOWN: u8 = 20;
+const GAMEPAD_BUTTON_UP: u8 = 21;
+const GAMEPAD_AXIS: u8 = 22;
+
+/// Builds up one encoded event
+struct Encoder(Vec<u8>);
+
+impl Encoder {
+    fn new(tag: u8, window_id: u32) -> Self {
+        let mut bytes = vec![tag];
+        bytes.extend(window_id.to_le_bytes());
+        Encoder(bytes)
+    }
+
+    fn u8(mut self, value: u8) -> Self {
+        self.0.push(value);
+        self
+    }
+
+    fn u32(mut self, value: u32) -> Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn i32(mut self, value: i32) -> Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn i64(mut self, value: i64) -> Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn f32(mut self, value: f32) -> Self {
+        self.0.extend(value.to_le_bytes());
+        self
+    }
+
+    fn str(mut self, value: &str) -> Self {
+        self.0.extend((value.len() as u32).to_le_bytes());
+        self.0.extend(value.as_bytes());
+        self
+    }
+}
+
+/// Reads encoded events back, failing on a truncated file
+struct Decoder<'a>(&'a [u8]);
+
+impl Decoder<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let (bytes, rest) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or_else(|| Error::Decode("recording ends in the middle of a frame".to_string()))?;
+        self.0 = rest;
+        Ok(*bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.take()?))
+    }
+
+    fn i32(&mut self) -> Result<i32, Error> {
+        Ok(i32::from_le_bytes(self.take()?))
+    }
+
+    fn i64(&mut self) -> Result<i64, Error> {
+        Ok(i64::from_le_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, Error> {
+        Ok(f32::from_le_bytes(self.take()?))
+    }
+
+    fn f64(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_le_bytes(self.take()?))
+    }
+
+    fn str(&mut self) -> Result<String, Error> {
+        let len = self.u32()? as usize;
+        if self.0.len() < len {
+            return Err(Error::Decode("recording ends in the middle of a frame".to_string()));
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        String::from_utf8(bytes.to_vec()).map_err(|e| Error::Decode(e.to_string()))
+    }
+}
+
+/// encode the input events maylib uses, and skip the rest.
+/// Gamepads are stored by slot, since SDL's ids change from run to run
+fn encode_event(get: &Maylib, event: &Event) -> Option<Vec<u8>> {
+    let encoded = match *event {
+        Event::KeyDown { window_id, scancode: Some(scancode), repeat, .. } => {
+            Encoder::new(KEY_DOWN, window_id).i32(scancode as i32).u8(repeat as u8)
+        }
+        Event::KeyUp { window_id, scancode: Some(scancode), .. } => {
+            Encoder::new(KEY_UP, window_id).i32(scancode as i32)
+        }
+        Event::TextInput { window_id, ref text, .. } => Encoder::new(TEXT_INPUT, window_id).str(text),
+        Event::TextEditing { window_id, ref text, start, length, .. } => {
+            Encoder::new(TEXT_EDITING, window_id).str(text).i32(start).i32(length)
+        }
+        Event::MouseMotion { window_id, x, y, xrel, yrel, .. } => {
+            Encoder::new(MOUSE_MOTION, window_id).i32(x).i32(y).i32(xrel).i32(yrel)
+        }
+        Event::MouseButtonDown { window_id, mouse_btn, clicks, x, y, .. } => {
+            Encoder::new(MOUSE_BUTTON_DOWN, window_id).u8(mouse_btn as u8).u8(clicks).i32(x).i32(y)
+        }
+        Event::MouseButtonUp { window_id, mouse_btn, clicks, x, y, .. } => {
+            Encoder::new(MOUSE_BUTTON_UP, window_id).u8(mouse_btn as u8).u8(clicks).i32(x).i32(y)
+        }
+        Event::MouseWheel { window_id, x, y, direction, precise_x, precise_y, .. } => {
+            let flipped = direction == MouseWheelDirection::Flipped;
+            Encoder::new(MOUSE_WHEEL, window_id)
+                .i32(x)
+                .i32(y)
+                .f32(precise_x)
+                .f32(precise_y)
+                .u8(flipped as u8)
+        }
+        Event::Window { window_id, win_event, .. } => {
+            let (kind, data1, data2) = encode_window_event(win_event)?;
+            Encoder::new(WINDOW, window_id).u8(kind).i32(data1).i32(data2)
+        }
+        Event::Quit { .. } => Encoder::new(QUIT, 0),
+        Event::DropBegin { window_id, .. } => Encoder::new(DROP_BEGIN, window_id),
+        Event::DropFile { window_id, ref filename, .. } => Encoder::new(DROP_FILE, window_id).str(filename),
+        Event::DropText { window_id, ref filename, .. } => Encoder::new(DROP_TEXT, window_id).str(filename),
+        Event::DropComplete { window_id, .. } => Encoder::new(DROP_COMPLETE, window_id),
+        Event::FingerDown { touch_id, finger_id, x, y, dx, dy, pressure, .. } => {
+            Encoder::new(FINGER_DOWN, 0).i64(touch_id).i64(finger_id).f32(x).f32(y).f32(dx).f32(dy).f32(pressure)
+        }
+        Event::FingerUp { touch_id, finger_id, x, y, dx, dy, pressure, .. } => {
+            Encoder::new(FINGER_UP, 0).i64(touch_id).i64(finger_id).f32(x).f32(y).f32(dx).f32(dy).f32(pressure)
+        }
+        Event::FingerMotion { touch_id, finger_id, x, y, dx, dy, pressure, .. } => {
+            Encoder::new(FINGER_MOTION, 0).i64(touch_id).i64(finger_id).f32(x).f32(y).f32(dx).f32(dy).f32(pressure)
+        }
+        Event::ControllerButtonDown { which, button, .. } => {
+            let slot = gamepad::find_slot(get, which)?;
+            Encoder::new(GAMEPAD_BUTTON_DOWN, 0).u32(slot as u32).str(&button.string())
+        }
+        Event::ControllerButtonUp { which, button, .. } => {
+            let slot = gamepad::find_slot(get, which)?;
+            Encoder::new(GAMEPAD_BUTTON_UP, 0).u32(slot as u32).str(&button.string())
+        }
+        Event::ControllerAxisMotion { which, axis, value, .. } => {
+            let slot = gamepad::find_slot(get, which)?;
+            Encoder::new(GAMEPAD_AXIS, 0).u32(slot as u32).str(&axis.string()).i32(value as i32)
+        }
+        _ => return None,
+    };
+    Some(encoded.0)
+}
+
+fn encode_window_event(event: WindowEvent) -> Option<(u8, i32, i32)> {
+    Some(match event {
+        WindowEvent::Shown => (1, 0, 0),
+        WindowEvent::Hidden => (2, 0, 0),
+        WindowEvent::Moved(x, y) => (3, x, y),
+        WindowEvent::Resized(w, h) => (4, w, h),
+        WindowEvent::SizeChanged(w, h) => (5, w, h),
+        WindowEvent::Minimized => (6, 0, 0),
+        WindowEvent::Maximized => (7, 0, 0),
+        WindowEvent::Restored => (8, 0, 0),
+        WindowEvent::Enter => (9, 0, 0),
+        WindowEvent::Leave => (10, 0, 0),
+        WindowEvent::FocusGained => (11, 0, 0),
+        WindowEvent::FocusLost => (12, 0, 0),
+        WindowEvent::Close => (13, 0, 0),
+        _ => return None,
+    })
+}
+
+fn decode_window_event(kind: u8, data1: i32, data2: i32) -> Result<WindowEvent, Error> {
+    Ok(match kind {
+        1 => WindowEvent::Shown,
+        2 => WindowEvent::Hidden,
+        3 => WindowEvent::Moved(data1, data2),
+        4 => WindowEvent::Resized(data1, data2),
+        5 => WindowEvent::SizeChanged(data1, data2),
+        6 => WindowEvent::Minimized,
+        7 => WindowEvent::Maximized,
+        8 => WindowEvent::Restored,
+        9 => WindowEvent::Enter,
+        10 => WindowEvent::Leave,
+        11 => WindowEvent::FocusGained,
+        12 => WindowEvent::FocusLost,
+        13 => WindowEvent::Close,
+        _ => return Err(Error::Decode(format!("unknown window event {} in recording", kind))),
+    })
+}
+
+fn decode_frames(bytes: &[u8]) -> Result<VecDeque<(f64, Vec<Event>)>, Error> {
+    let mut decoder = Decoder(bytes);
+    if decoder.take::<8>().ok().as_ref() != Some(MAGIC) {
+        return Err(Error::Decode("not a maylib recording".to_string()));
+    }
+    let mut frames = VecDeque::new();
+    while !decoder.0.is_empty() {
+        let delta = decoder.f64()?;
+        let count = decoder.u32()?;
+        let events = (0..count)
+            .map(|_| decode_event(&mut decoder))
+            .collect::<Result<Vec<_>, _>>()?;
+        frames.push_back((delta, events));
+    }
+    Ok(frames)
+}
+
+fn decode_event(decoder: &mut Decoder) -> Result<Event, Error> {
+    let tag = decoder.u8()?;
+    let window_id = decoder.u32()?;
+    let timestamp = 0;
+    Ok(match tag {
+        KEY_DOWN | KEY_UP => {
+            let scancode = Scancode::from_i32(decoder.i32()?);
+            let keycode = scancode.and_then(Keycode::from_scancode);
+            let keymod = Mod::NOMOD;
+            if tag == KEY_DOWN {
+                let repeat = decoder.u8()? != 0;
+                Event::KeyDown { timestamp, window_id, keycode, scancode, keymod, repeat }
+            } else {
+                Event::KeyUp { timestamp, window_id, keycode, scancode, keymod, repeat: false }
+            }
+        }
+        TEXT_INPUT => Event::TextInput { timestamp, window_id, text: decoder.str()? },
+        TEXT_EDITING => Event::TextEditing {
+            timestamp,
+            window_id,
+            text: decoder.str()?,
+            start: decoder.i32()?,
+            length: decoder.i32()?,
+        },
+        MOUSE_MOTION => Event::MouseMotion {
+            timestamp,
+            window_id,
+            which: 0,
+            mousestate: MouseState::from_sdl_state(0),
+            x: decoder.i32()?,
+            y: decoder.i32()?,
+            xrel: decoder.i32()?,
+            yrel: decoder.i32()?,
+        },
+        MOUSE_BUTTON_DOWN | MOUSE_BUTTON_UP => {
+            let mouse_btn = MouseButton::from_ll(decoder.u8()?);
+            let clicks = decoder.u8()?;
+            let (x, y) = (decoder.i32()?, decoder.i32()?);
+            if tag == MOUSE_BUTTON_DOWN {
+                Event::MouseButtonDown { timestamp, window_id, which: 0, mouse_btn, clicks, x, y }
+            } else {
+                Event::MouseButtonUp { timestamp, window_id, which: 0, mouse_btn, clicks, x, y }
+            }
+        }
+        MOUSE_WHEEL => Event::MouseWheel {
+            timestamp,
+            window_id,
+            which: 0,
+            x: decoder.i32()?,
+            y: decoder.i32()?,
+            precise_x: decoder.f32()?,
+            precise_y: decoder.f32()?,
+            direction: if decoder.u8()? != 0 {
+                MouseWheelDirection::Flipped
+            } else {
+                MouseWheelDirection::Normal
+            },
+            mouse_x: 0,
+            mouse_y: 0,
+        },
+        WINDOW => {
+            let kind = decoder.u8()?;
+            let win_event = decode_window_event(kind, decoder.i32()?, decoder.i32()?)?;
+            Event::Window { timestamp, window_id, win_event }
+        }
+        QUIT => Event::Quit { timestamp },
+        DROP_BEGIN => Event::DropBegin { timestamp, window_id },
+        DROP_FILE => Event::DropFile { timestamp, window_id, filename: decoder.str()? },
+        DROP_TEXT => Event::DropText { timestamp, window_id, filename: decoder.str()? },
+        DROP_COMPLETE => Event::DropComplete { timestamp, window_id },
+        FINGER_DOWN | FINGER_UP | FINGER_MOTION => {
+            let (touch_id, finger_id) = (decoder.i64()?, decoder.i64()?);
+            let (x, y, dx, dy, pressure) =
+                (decoder.f32()?, decoder.f32()?, decoder.f32()?, decoder.f32()?, decoder.f32()?);
+            match tag {
+                FINGER_DOWN => Event::FingerDown { timestamp, touch_id, finger_id, x, y, dx, dy, pressure },
+                FINGER_UP => Event::FingerUp { timestamp, touch_id, finger_id, x, y, dx, dy, pressure },
+                _ => Event::FingerMotion { timestamp, touch_id, finger_id, x, y, dx, dy, pressure },
+            }
+        }
+        // The slot stands in for SDL's id until `replay_event` swaps in the gamepad playing it
+        GAMEPAD_ADDED => Event::ControllerDeviceAdded { timestamp, which: decoder.u32()? },
+        GAMEPAD_REMOVED => Event::ControllerDeviceRemoved { timestamp, which: decoder.u32()? },
+        GAMEPAD_BUTTON_DOWN | GAMEPAD_BUTTON_UP => {
+            let which = decoder.u32()?;
+            let name = decoder.str()?;
+            let button = Button::from_string(&name)
+                .ok_or_else(|| Error::Decode(format!("unknown gamepad button {} in recording", name)))?;
+            if tag == GAMEPAD_BUTTON_DOWN {
+                Event::ControllerButtonDown { timestamp, which, button }
+            } else {
+                Event::ControllerButtonUp { timestamp, which, button }
+            }
+        }
+        GAMEPAD_AXIS => {
+            let which = decoder.u32()?;
+            let name = decoder.str()?;
+            let axis = Axis::from_string(&name)
+                .ok_or_else(|| Error::Decode(format!("unknown gamepad axis {} in recording", name)))?;
+            let value = decoder.i32()?.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+            Event::ControllerAxisMotion { timestamp, which, axis, value }
+        }
+        _ => return Err(Error::Decode(format!("unknown event {} in recording", tag))),
+    })
+}
@@ -1,109 +1,176 @@
-use crate::types::{Color};
+use crate::error::Error;
+use crate::types::{Color, WindowHandle};
 use sdl2::gfx::primitives::DrawRenderer;
 use sdl2::pixels;
 use sdl2::rect::{Point, Rect};
-use crate::core::MAYLIB;
+use crate::core::{get_current_window, try_get_current_window, MAYLIB};
 
 pub fn draw_pixel(x: i32, y: i32, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .set_draw_color(color);
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .draw_point(Point::new(x, y))
-        .expect("Failed to draw pixel");
+    get_current_window().draw_pixel(x, y, color);
+}
+
+pub fn try_draw_pixel(x: i32, y: i32, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_pixel(x, y, color)
 }
 
 pub fn draw_line(start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .set_draw_color(color);
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .draw_line(Point::new(start_x, start_y), Point::new(end_x, end_y))
-        .expect("Failed to draw line");
+    get_current_window().draw_line(start_x, start_y, end_x, end_y, color);
+}
+
+pub fn try_draw_line(start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_line(start_x, start_y, end_x, end_y, color)
 }
 
 pub fn draw_circle(center_x: i16, center_y: i16, radius: i16, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .filled_circle(center_x, center_y, radius, pixels::Color::from(color))
-        .expect("Failed to fill circle");
+    get_current_window().draw_circle(center_x, center_y, radius, color);
+}
+
+pub fn try_draw_circle(center_x: i16, center_y: i16, radius: i16, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_circle(center_x, center_y, radius, color)
 }
 
 pub fn draw_circle_lines(center_x: i16, center_y: i16, radius: i16, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .circle(center_x, center_y, radius, pixels::Color::from(color))
-        .expect("Failed to draw circle");
+    get_current_window().draw_circle_lines(center_x, center_y, radius, color);
+}
+
+pub fn try_draw_circle_lines(center_x: i16, center_y: i16, radius: i16, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_circle_lines(center_x, center_y, radius, color)
 }
 
 pub fn draw_ellipse(x: i16, y: i16, w: i16, h: i16, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .filled_ellipse(x, y, w, h, pixels::Color::from(color))
-        .expect("Failed to fill ellipse");
+    get_current_window().draw_ellipse(x, y, w, h, color);
+}
+
+pub fn try_draw_ellipse(x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_ellipse(x, y, w, h, color)
 }
 
 pub fn draw_ellipse_lines(x: i16, y: i16, w: i16, h: i16, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .ellipse(x, y, w, h, pixels::Color::from(color))
-        .expect("Failed to draw ellipse");
+    get_current_window().draw_ellipse_lines(x, y, w, h, color);
+}
+
+pub fn try_draw_ellipse_lines(x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_ellipse_lines(x, y, w, h, color)
 }
 
 pub fn draw_rectangle(x: i16, y: i16, w: i16, h: i16, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .set_draw_color(color);
-    let rect = Rect::new(x as i32, y as i32, w as u32, h as u32);
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .fill_rect(rect)
-        .expect("Failed to fill rectangle");
+    get_current_window().draw_rectangle(x, y, w, h, color);
+}
+
+pub fn try_draw_rectangle(x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_rectangle(x, y, w, h, color)
 }
 
 pub fn draw_rectangle_lines(x: i16, y: i16, w: i16, h: i16, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    get.windows
-        .get_mut(&current_window)
-        .expect("Window should be valid if loaded from switch_window")
-        .canvas
-        .rectangle(x, y, w, h, pixels::Color::from(color))
-        .expect("Failed to draw rectangle");
-}
\ No newline at end of file
+    get_current_window().draw_rectangle_lines(x, y, w, h, color);
+}
+
+pub fn try_draw_rectangle_lines(x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_rectangle_lines(x, y, w, h, color)
+}
+
+impl WindowHandle {
+    pub fn draw_pixel(self, x: i32, y: i32, color: Color) {
+        self.try_draw_pixel(x, y, color)
+            .expect("Failed to draw pixel");
+    }
+
+    pub fn try_draw_pixel(self, x: i32, y: i32, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let canvas = &mut get.window_mut(self)?.canvas;
+        canvas.set_draw_color(color);
+        canvas.draw_point(Point::new(x, y)).map_err(Error::Sdl)
+    }
+
+    pub fn draw_line(self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) {
+        self.try_draw_line(start_x, start_y, end_x, end_y, color)
+            .expect("Failed to draw line");
+    }
+
+    pub fn try_draw_line(self, start_x: i32, start_y: i32, end_x: i32, end_y: i32, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let canvas = &mut get.window_mut(self)?.canvas;
+        canvas.set_draw_color(color);
+        canvas
+            .draw_line(Point::new(start_x, start_y), Point::new(end_x, end_y))
+            .map_err(Error::Sdl)
+    }
+
+    pub fn draw_circle(self, center_x: i16, center_y: i16, radius: i16, color: Color) {
+        self.try_draw_circle(center_x, center_y, radius, color)
+            .expect("Failed to fill circle");
+    }
+
+    pub fn try_draw_circle(self, center_x: i16, center_y: i16, radius: i16, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .canvas
+            .filled_circle(center_x, center_y, radius, pixels::Color::from(color))
+            .map_err(Error::Sdl)
+    }
+
+    pub fn draw_circle_lines(self, center_x: i16, center_y: i16, radius: i16, color: Color) {
+        self.try_draw_circle_lines(center_x, center_y, radius, color)
+            .expect("Failed to draw circle");
+    }
+
+    pub fn try_draw_circle_lines(self, center_x: i16, center_y: i16, radius: i16, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .canvas
+            .circle(center_x, center_y, radius, pixels::Color::from(color))
+            .map_err(Error::Sdl)
+    }
+
+    pub fn draw_ellipse(self, x: i16, y: i16, w: i16, h: i16, color: Color) {
+        self.try_draw_ellipse(x, y, w, h, color)
+            .expect("Failed to fill ellipse");
+    }
+
+    pub fn try_draw_ellipse(self, x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .canvas
+            .filled_ellipse(x, y, w, h, pixels::Color::from(color))
+            .map_err(Error::Sdl)
+    }
+
+    pub fn draw_ellipse_lines(self, x: i16, y: i16, w: i16, h: i16, color: Color) {
+        self.try_draw_ellipse_lines(x, y, w, h, color)
+            .expect("Failed to draw ellipse");
+    }
+
+    pub fn try_draw_ellipse_lines(self, x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .canvas
+            .ellipse(x, y, w, h, pixels::Color::from(color))
+            .map_err(Error::Sdl)
+    }
+
+    pub fn draw_rectangle(self, x: i16, y: i16, w: i16, h: i16, color: Color) {
+        self.try_draw_rectangle(x, y, w, h, color)
+            .expect("Failed to fill rectangle");
+    }
+
+    pub fn try_draw_rectangle(self, x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let canvas = &mut get.window_mut(self)?.canvas;
+        canvas.set_draw_color(color);
+        let rect = Rect::new(x as i32, y as i32, w as u32, h as u32);
+        canvas.fill_rect(rect).map_err(Error::Sdl)
+    }
+
+    pub fn draw_rectangle_lines(self, x: i16, y: i16, w: i16, h: i16, color: Color) {
+        self.try_draw_rectangle_lines(x, y, w, h, color)
+            .expect("Failed to draw rectangle");
+    }
+
+    pub fn try_draw_rectangle_lines(self, x: i16, y: i16, w: i16, h: i16, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        get.window_mut(self)?
+            .canvas
+            .rectangle(x, y, w, h, pixels::Color::from(color))
+            .map_err(Error::Sdl)
+    }
+}
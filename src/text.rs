@@ -1,4 +1,5 @@
-use crate::types::{Color};
+use crate::error::Error;
+use crate::types::{Color, WindowHandle};
 use fontdue_sdl2::fontdue::FontSettings;
 use fontdue_sdl2::fontdue::layout::{CoordinateSystem, Layout, TextStyle};
 use fontdue_sdl2::{FontTexture, fontdue};
@@ -7,49 +8,70 @@ use std::fs::File;
 use std::io::Read;
 use sdl2::render::Canvas;
 use sdl2::sys::Window;
-use crate::core::MAYLIB;
+use crate::core::{get_current_window, try_get_current_window, MAYLIB};
 
 pub struct Font {
     font: fontdue::Font,
 }
 
 pub fn load_font_file(path: &str) -> Font {
+    try_load_font_file(path).expect("Failed to load font")
+}
+
+pub fn try_load_font_file(path: &str) -> Result<Font, Error> {
     let mut buffer: Vec<u8> = vec![];
-    let mut file = File::open(path).expect("Failed to load font");
-    file.read_to_end(&mut buffer).expect("TODO: panic message");
-    Font {
-        font: fontdue::Font::from_bytes(buffer, FontSettings::default())
-            .expect("Failed to load font"),
-    }
+    let mut file = File::open(path)?;
+    file.read_to_end(&mut buffer)?;
+    try_load_font_bytes(&buffer)
 }
 
 pub fn load_font_bytes(bytes: &[u8]) -> Font {
-    Font {
+    try_load_font_bytes(bytes).expect("Failed to load font")
+}
+
+pub fn try_load_font_bytes(bytes: &[u8]) -> Result<Font, Error> {
+    Ok(Font {
         font: fontdue::Font::from_bytes(bytes, FontSettings::default())
-            .expect("Failed to load font"),
-    }
+            .map_err(|e| Error::Font(e.to_string()))?,
+    })
 }
 
 pub fn draw_text(font: &Font, text: &str, size: f32, x: i32, y: i32, color: Color) {
-    let mut get = MAYLIB.lock().expect("Should be able to lock");
-    let current_window = get.current_window;
-    let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
-    let fonts = [font.font.clone()];
+    get_current_window().draw_text(font, text, size, x, y, color);
+}
+
+pub fn try_draw_text(font: &Font, text: &str, size: f32, x: i32, y: i32, color: Color) -> Result<(), Error> {
+    try_get_current_window()?.try_draw_text(font, text, size, x, y, color)
+}
+
+impl WindowHandle {
+    pub fn draw_text(self, font: &Font, text: &str, size: f32, x: i32, y: i32, color: Color) {
+        self.try_draw_text(font, text, size, x, y, color)
+            .expect("Failed to draw text");
+    }
+
+    pub fn try_draw_text(self, font: &Font, text: &str, size: f32, x: i32, y: i32, color: Color) -> Result<(), Error> {
+        let mut get = MAYLIB.lock().expect("Should be able to lock");
+        let mut layout = Layout::new(CoordinateSystem::PositiveYDown);
+        let fonts = [font.font.clone()];
 
-    layout.reset(&fontdue::layout::LayoutSettings {
-        x: x as f32, // Apply X offset
-        y: y as f32, // Apply Y offset
-        ..Default::default()
-    });
+        layout.reset(&fontdue::layout::LayoutSettings {
+            x: x as f32, // Apply X offset
+            y: y as f32, // Apply Y offset
+            ..Default::default()
+        });
 
-    layout.append(
-        &fonts,
-        &TextStyle::with_user_data(text, size, 0, pixels::Color::from(color)),
-    );
+        layout.append(
+            &fonts,
+            &TextStyle::with_user_data(text, size, 0, pixels::Color::from(color)),
+        );
 
-    if let Some(window) = get.windows.get_mut(&current_window) {
-        let mut font_texture = FontTexture::new(&window.texture).expect("Failed to load font");
-        let _ = font_texture.draw_text(&mut window.canvas, &fonts, layout.glyphs().as_slice());
+        let window = get.window_mut(self)?;
+        let mut font_texture = FontTexture::new(&window.texture)
+            .map_err(|e| Error::Font(e.to_string()))?;
+        font_texture
+            .draw_text(&mut window.canvas, &fonts, layout.glyphs().as_slice())
+            .map_err(|e| Error::Font(e.to_string()))
     }
 }
 
@@ -0,0 +1,327 @@
+use crate::core::{get_current_window, try_get_current_window, MAYLIB};
+use crate::error::Error;
+use crate::types::{Gesture, GestureState, Maylib, TouchPoint, WindowHandle};
+use sdl2::event::Event;
+
+/// How long a finger must stay still to count as a hold, in seconds
+const HOLD_TIME: f64 = 0.5;
+/// How long after a tap a second tap counts as a double tap, in seconds
+const DOUBLE_TAP_TIME: f64 = 0.3;
+/// How far a finger can move, in pixels, and still count as a tap or hold
+const MOVE_DISTANCE: f32 = 10.0;
+/// How fast a drag must be going when released to count as a swipe, in pixels per second
+const SWIPE_SPEED: f32 = 500.0;
+
+/// update touch points and gestures from an SDL event, called by `begin_drawing`.
+/// Returns the window the finger belongs to and where it is, for turning the event into a maylib one
+pub(crate) fn record_event(get: &mut Maylib, event: &Event) -> Option<(u32, TouchPoint)> {
+    let (window_id, point) = touch_point(get, event)?;
+    let time = get.time();
+    let window = get.windows.get_mut(&window_id)?;
+    let finger_id = point.id;
+    let input = &mut window.input;
+    match *event {
+        Event::FingerDown { .. } => {
+            input.touch_points.push(point);
+            input.gestures.finger_down(&input.touch_points, time);
+        }
+        Event::FingerMotion { .. } => {
+            if let Some(existing) = input.touch_points.iter_mut().find(|p| p.id == finger_id) {
+                *existing = point;
+            }
+            input.gestures.finger_moved(&input.touch_points);
+        }
+        Event::FingerUp { .. } => {
+            input.touch_points.retain(|p| p.id != finger_id);
+            input.gestures.finger_up(&input.touch_points, point, time);
+        }
+        _ => {}
+    }
+    Some((window_id, point))
+}
+
+/// find which window a finger event is for, and where the finger is in it
+fn touch_point(get: &Maylib, event: &Event) -> Option<(u32, TouchPoint)> {
+    let (finger_id, x, y, down) = match *event {
+        Event::FingerDown { finger_id, x, y, .. } => (finger_id, x, y, true),
+        Event::FingerUp { finger_id, x, y, .. } | Event::FingerMotion { finger_id, x, y, .. } => {
+            (finger_id, x, y, false)
+        }
+        _ => return None,
+    };
+    // SDL doesn't say which window a finger event is for, so a new finger goes to the focused one
+    // and stays with it until it lifts, even if focus moves
+    let (id, window) = if down {
+        get.windows
+            .iter()
+            .find(|(_, window)| window.focused)
+            .or_else(|| get.windows.iter().find(|(_, window)| window.hovered))?
+    } else {
+        get.windows
+            .iter()
+            .find(|(_, window)| window.input.touch_points.iter().any(|p| p.id == finger_id))?
+    };
+    // Finger positions come from 0 to 1 across the window
+    let (width, height) = window.window.size();
+    let point = TouchPoint {
+        id: finger_id,
+        x: x * width as f32,
+        y: y * height as f32,
+    };
+    Some((*id, point))
+}
+
+fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+fn pinch_distance(points: &[TouchPoint]) -> f32 {
+    distance((points[0].x, points[0].y), (points[1].x, points[1].y))
+}
+
+impl GestureState {
+    /// end one-frame gestures and start holds, called by `begin_drawing` before polling
+    pub(crate) fn begin_frame(&mut self, touching: bool, time: f64) {
+        match self.current {
+            Gesture::Tap
+            | Gesture::DoubleTap
+            | Gesture::SwipeRight
+            | Gesture::SwipeLeft
+            | Gesture::SwipeUp
+            | Gesture::SwipeDown => self.current = Gesture::None,
+            _ => {}
+        }
+        if touching && !self.moved && !self.multi_touch && time - self.start_time >= HOLD_TIME {
+            self.current = Gesture::Hold;
+        }
+    }
+
+    pub(crate) fn finger_down(&mut self, points: &[TouchPoint], time: f64) {
+        match points {
+            [first] => {
+                *self = GestureState {
+                    start_time: time,
+                    start: (first.x, first.y),
+                    last_tap_time: self.last_tap_time,
+                    last_tap: self.last_tap,
+                    pinch_scale: 1.0,
+                    ..GestureState::default()
+                };
+            }
+            [_, _] => {
+                self.multi_touch = true;
+                self.current = Gesture::None;
+                self.pinch_start_distance = pinch_distance(points);
+                self.pinch_scale = 1.0;
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn finger_moved(&mut self, points: &[TouchPoint]) {
+        match points {
+            [first] if !self.multi_touch => {
+                self.drag_vector = (first.x - self.start.0, first.y - self.start.1);
+                if self.moved || distance(self.start, (first.x, first.y)) > MOVE_DISTANCE {
+                    self.moved = true;
+                    self.current = Gesture::Drag;
+                }
+            }
+            [_, _, ..] => {
+                let distance = pinch_distance(points);
+                if self.pinch_start_distance > 0.0 {
+                    self.pinch_scale = distance / self.pinch_start_distance;
+                }
+                if (distance - self.pinch_start_distance).abs() > MOVE_DISTANCE {
+                    self.current = if distance > self.pinch_start_distance {
+                        Gesture::PinchOut
+                    } else {
+                        Gesture::PinchIn
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn finger_up(&mut self, points: &[TouchPoint], lifted: TouchPoint, time: f64) {
+        if self.multi_touch {
+            // Wait for every finger to lift before recognising anything new
+            self.current = Gesture::None;
+            return;
+        }
+        if !points.is_empty() {
+            return;
+        }
+        let held = time - self.start_time;
+        self.current = match self.current {
+            Gesture::Drag => {
+                let (dx, dy) = self.drag_vector;
+                let speed = distance((0.0, 0.0), (dx, dy)) / held.max(f64::EPSILON) as f32;
+                if speed < SWIPE_SPEED {
+                    Gesture::None
+                } else if dx.abs() > dy.abs() {
+                    if dx > 0.0 { Gesture::SwipeRight } else { Gesture::SwipeLeft }
+                } else if dy > 0.0 {
+                    Gesture::SwipeDown
+                } else {
+                    Gesture::SwipeUp
+                }
+            }
+            Gesture::None if !self.moved && held < HOLD_TIME => {
+                let position = (lifted.x, lifted.y);
+                let double = self.last_tap_time.is_some_and(|last| time - last < DOUBLE_TAP_TIME)
+                    && distance(self.last_tap, position) < MOVE_DISTANCE;
+                if double {
+                    // A third tap starts a new double tap instead of making another one
+                    self.last_tap_time = None;
+                    Gesture::DoubleTap
+                } else {
+                    self.last_tap_time = Some(time);
+                    self.last_tap = position;
+                    Gesture::Tap
+                }
+            }
+            _ => Gesture::None,
+        };
+    }
+}
+
+/// get how many fingers are on the touchscreen in the current window
+pub fn get_touch_point_count() -> usize {
+    get_current_window().touch_point_count()
+}
+
+pub fn try_get_touch_point_count() -> Result<usize, Error> {
+    try_get_current_window()?.try_touch_point_count()
+}
+
+/// get where a finger is in the current window, in the order fingers went down
+pub fn get_touch_position(index: usize) -> Option<(f32, f32)> {
+    get_current_window().touch_position(index)
+}
+
+pub fn try_get_touch_position(index: usize) -> Result<Option<(f32, f32)>, Error> {
+    try_get_current_window()?.try_touch_position(index)
+}
+
+/// get the id of a finger in the current window, which stays the same while it is down
+pub fn get_touch_point_id(index: usize) -> Option<i64> {
+    get_current_window().touch_point_id(index)
+}
+
+pub fn try_get_touch_point_id(index: usize) -> Result<Option<i64>, Error> {
+    try_get_current_window()?.try_touch_point_id(index)
+}
+
+/// get every finger on the touchscreen in the current window
+pub fn get_touch_points() -> Vec<TouchPoint> {
+    get_current_window().touch_points()
+}
+
+pub fn try_get_touch_points() -> Result<Vec<TouchPoint>, Error> {
+    try_get_current_window()?.try_touch_points()
+}
+
+/// get the gesture happening in the current window
+pub fn get_gesture_detected() -> Gesture {
+    get_current_window().gesture_detected()
+}
+
+pub fn try_get_gesture_detected() -> Result<Gesture, Error> {
+    try_get_current_window()?.try_gesture_detected()
+}
+
+/// check if a gesture is happening in the current window
+pub fn is_gesture_detected(gesture: Gesture) -> bool {
+    get_gesture_detected() == gesture
+}
+
+/// get how far the finger has moved since it went down, for drags and swipes
+pub fn get_gesture_drag_vector() -> (f32, f32) {
+    get_current_window().gesture_drag_vector()
+}
+
+pub fn try_get_gesture_drag_vector() -> Result<(f32, f32), Error> {
+    try_get_current_window()?.try_gesture_drag_vector()
+}
+
+/// get how far apart two fingers are compared to when the pinch started. Above 1 is pinching out
+pub fn get_gesture_pinch_scale() -> f32 {
+    get_current_window().gesture_pinch_scale()
+}
+
+pub fn try_get_gesture_pinch_scale() -> Result<f32, Error> {
+    try_get_current_window()?.try_gesture_pinch_scale()
+}
+
+impl WindowHandle {
+    fn with_touch<T>(self, f: impl FnOnce(&[TouchPoint], &GestureState) -> T) -> Result<T, Error> {
+        let get = MAYLIB.lock().expect("Should be able to lock");
+        let input = &get.window(self)?.input;
+        Ok(f(&input.touch_points, &input.gestures))
+    }
+
+    pub fn touch_point_count(self) -> usize {
+        self.try_touch_point_count()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_touch_point_count(self) -> Result<usize, Error> {
+        self.with_touch(|points, _| points.len())
+    }
+
+    pub fn touch_position(self, index: usize) -> Option<(f32, f32)> {
+        self.try_touch_position(index)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_touch_position(self, index: usize) -> Result<Option<(f32, f32)>, Error> {
+        self.with_touch(|points, _| points.get(index).map(|point| (point.x, point.y)))
+    }
+
+    pub fn touch_point_id(self, index: usize) -> Option<i64> {
+        self.try_touch_point_id(index)
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_touch_point_id(self, index: usize) -> Result<Option<i64>, Error> {
+        self.with_touch(|points, _| points.get(index).map(|point| point.id))
+    }
+
+    pub fn touch_points(self) -> Vec<TouchPoint> {
+        self.try_touch_points()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_touch_points(self) -> Result<Vec<TouchPoint>, Error> {
+        self.with_touch(|points, _| points.to_vec())
+    }
+
+    pub fn gesture_detected(self) -> Gesture {
+        self.try_gesture_detected()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_gesture_detected(self) -> Result<Gesture, Error> {
+        self.with_touch(|_, gestures| gestures.current)
+    }
+
+    pub fn gesture_drag_vector(self) -> (f32, f32) {
+        self.try_gesture_drag_vector()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_gesture_drag_vector(self) -> Result<(f32, f32), Error> {
+        self.with_touch(|_, gestures| gestures.drag_vector)
+    }
+
+    pub fn gesture_pinch_scale(self) -> f32 {
+        self.try_gesture_pinch_scale()
+            .expect("Window handle should be valid")
+    }
+
+    pub fn try_gesture_pinch_scale(self) -> Result<f32, Error> {
+        self.with_touch(|_, gestures| gestures.pinch_scale)
+    }
+}
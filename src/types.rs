@@ -1,17 +1,56 @@
+use crate::error::Error;
+use crate::gamepad::GamepadHotplug;
 use rodio::{OutputStream, OutputStreamHandle};
 use sdl2::clipboard::ClipboardUtil;
+use sdl2::controller::GameController;
+use sdl2::hint::Hint;
 use sdl2::image::InitFlag;
-use sdl2::keyboard::Scancode;
-use sdl2::mouse::MouseUtil;
+use sdl2::keyboard;
+use sdl2::mouse::{Cursor, MouseUtil};
 use sdl2::render::{Canvas, TextureCreator};
-use sdl2::sys::SDL_Scancode;
-use sdl2::{EventPump, Sdl, TimerSubsystem, VideoSubsystem, pixels, video};
-use std::collections::HashMap;
+use sdl2::sys::{SDL_KeyCode, SDL_Scancode, SDLK_SCANCODE_MASK};
+use sdl2::{EventPump, GameControllerSubsystem, Sdl, TimerSubsystem, VideoSubsystem, pixels, video};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::time::Instant;
 
+/// The current window id when no window is open
+pub(crate) const NO_WINDOW: u32 = u32::MAX;
+
+/// Decides whether a window may close. Returning false vetoes the close
+pub(crate) type CloseRequestedCallback = Box<dyn FnMut(WindowHandle) -> bool + Send>;
+
+/// Told about every window after it closes
+pub(crate) type WindowClosedCallback = Box<dyn FnMut(WindowHandle) + Send>;
+
+/// Told the slot of a gamepad that was connected or disconnected
+pub(crate) type GamepadCallback = Box<dyn FnMut(usize) + Send>;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum MouseButton {
     Left,
     Right,
     Middle,
+    /// The back button on mice that have one
+    X1,
+    /// The forward button on mice that have one
+    X2,
+}
+impl MouseButton {
+    /// `None` for buttons SDL doesn't know
+    pub(crate) fn from_sdl(button: sdl2::mouse::MouseButton) -> Option<MouseButton> {
+        match button {
+            sdl2::mouse::MouseButton::Left => Some(MouseButton::Left),
+            sdl2::mouse::MouseButton::Right => Some(MouseButton::Right),
+            sdl2::mouse::MouseButton::Middle => Some(MouseButton::Middle),
+            sdl2::mouse::MouseButton::X1 => Some(MouseButton::X1),
+            sdl2::mouse::MouseButton::X2 => Some(MouseButton::X2),
+            sdl2::mouse::MouseButton::Unknown => None,
+        }
+    }
 }
 impl From<MouseButton> for sdl2::mouse::MouseButton {
     fn from(button: MouseButton) -> Self {
@@ -19,11 +58,282 @@ impl From<MouseButton> for sdl2::mouse::MouseButton {
             MouseButton::Left => sdl2::mouse::MouseButton::Left,
             MouseButton::Right => sdl2::mouse::MouseButton::Right,
             MouseButton::Middle => sdl2::mouse::MouseButton::Middle,
+            MouseButton::X1 => sdl2::mouse::MouseButton::X1,
+            MouseButton::X2 => sdl2::mouse::MouseButton::X2,
+        }
+    }
+}
+
+/// A gamepad button, named after where it is on an Xbox controller
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GamepadButton {
+    /// The bottom face button
+    A,
+    /// The right face button
+    B,
+    /// The left face button
+    X,
+    /// The top face button
+    Y,
+    Back,
+    Guide,
+    Start,
+    LeftStick,
+    RightStick,
+    LeftShoulder,
+    RightShoulder,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    /// Share, capture or mute, depending on the gamepad
+    Misc1,
+    Paddle1,
+    Paddle2,
+    Paddle3,
+    Paddle4,
+    Touchpad,
+}
+impl From<GamepadButton> for sdl2::controller::Button {
+    fn from(button: GamepadButton) -> Self {
+        match button {
+            GamepadButton::A => sdl2::controller::Button::A,
+            GamepadButton::B => sdl2::controller::Button::B,
+            GamepadButton::X => sdl2::controller::Button::X,
+            GamepadButton::Y => sdl2::controller::Button::Y,
+            GamepadButton::Back => sdl2::controller::Button::Back,
+            GamepadButton::Guide => sdl2::controller::Button::Guide,
+            GamepadButton::Start => sdl2::controller::Button::Start,
+            GamepadButton::LeftStick => sdl2::controller::Button::LeftStick,
+            GamepadButton::RightStick => sdl2::controller::Button::RightStick,
+            GamepadButton::LeftShoulder => sdl2::controller::Button::LeftShoulder,
+            GamepadButton::RightShoulder => sdl2::controller::Button::RightShoulder,
+            GamepadButton::DpadUp => sdl2::controller::Button::DPadUp,
+            GamepadButton::DpadDown => sdl2::controller::Button::DPadDown,
+            GamepadButton::DpadLeft => sdl2::controller::Button::DPadLeft,
+            GamepadButton::DpadRight => sdl2::controller::Button::DPadRight,
+            GamepadButton::Misc1 => sdl2::controller::Button::Misc1,
+            GamepadButton::Paddle1 => sdl2::controller::Button::Paddle1,
+            GamepadButton::Paddle2 => sdl2::controller::Button::Paddle2,
+            GamepadButton::Paddle3 => sdl2::controller::Button::Paddle3,
+            GamepadButton::Paddle4 => sdl2::controller::Button::Paddle4,
+            GamepadButton::Touchpad => sdl2::controller::Button::Touchpad,
+        }
+    }
+}
+impl From<sdl2::controller::Button> for GamepadButton {
+    fn from(button: sdl2::controller::Button) -> Self {
+        match button {
+            sdl2::controller::Button::A => GamepadButton::A,
+            sdl2::controller::Button::B => GamepadButton::B,
+            sdl2::controller::Button::X => GamepadButton::X,
+            sdl2::controller::Button::Y => GamepadButton::Y,
+            sdl2::controller::Button::Back => GamepadButton::Back,
+            sdl2::controller::Button::Guide => GamepadButton::Guide,
+            sdl2::controller::Button::Start => GamepadButton::Start,
+            sdl2::controller::Button::LeftStick => GamepadButton::LeftStick,
+            sdl2::controller::Button::RightStick => GamepadButton::RightStick,
+            sdl2::controller::Button::LeftShoulder => GamepadButton::LeftShoulder,
+            sdl2::controller::Button::RightShoulder => GamepadButton::RightShoulder,
+            sdl2::controller::Button::DPadUp => GamepadButton::DpadUp,
+            sdl2::controller::Button::DPadDown => GamepadButton::DpadDown,
+            sdl2::controller::Button::DPadLeft => GamepadButton::DpadLeft,
+            sdl2::controller::Button::DPadRight => GamepadButton::DpadRight,
+            sdl2::controller::Button::Misc1 => GamepadButton::Misc1,
+            sdl2::controller::Button::Paddle1 => GamepadButton::Paddle1,
+            sdl2::controller::Button::Paddle2 => GamepadButton::Paddle2,
+            sdl2::controller::Button::Paddle3 => GamepadButton::Paddle3,
+            sdl2::controller::Button::Paddle4 => GamepadButton::Paddle4,
+            sdl2::controller::Button::Touchpad => GamepadButton::Touchpad,
+        }
+    }
+}
+
+/// Something a player can press to trigger an action
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum InputBinding {
+    /// A key in the current window, following the keyboard layout
+    Key(Keycode),
+    /// A mouse button in the current window
+    Mouse(MouseButton),
+    /// A button on any connected gamepad
+    Gamepad(GamepadButton),
+}
+
+impl InputBinding {
+    /// get a name for this binding that `InputBinding::from_name` reads back, like "key:Space" or "gamepad:a"
+    pub fn name(self) -> String {
+        match self {
+            InputBinding::Key(keycode) => format!("key:{}", keycode.name()),
+            InputBinding::Mouse(button) => {
+                let name = match button {
+                    MouseButton::Left => "left",
+                    MouseButton::Right => "right",
+                    MouseButton::Middle => "middle",
+                    MouseButton::X1 => "x1",
+                    MouseButton::X2 => "x2",
+                };
+                format!("mouse:{}", name)
+            }
+            InputBinding::Gamepad(button) => {
+                format!("gamepad:{}", sdl2::controller::Button::from(button).string())
+            }
+        }
+    }
+
+    /// find a binding by the name `InputBinding::name` gives it
+    pub fn from_name(name: &str) -> Option<InputBinding> {
+        let (kind, name) = name.split_once(':')?;
+        match kind {
+            "key" => Keycode::from_name(name).map(InputBinding::Key),
+            "mouse" => {
+                let button = match name {
+                    "left" => MouseButton::Left,
+                    "right" => MouseButton::Right,
+                    "middle" => MouseButton::Middle,
+                    "x1" => MouseButton::X1,
+                    "x2" => MouseButton::X2,
+                    _ => return None,
+                };
+                Some(InputBinding::Mouse(button))
+            }
+            "gamepad" => sdl2::controller::Button::from_string(name)
+                .map(|button| InputBinding::Gamepad(button.into())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InputBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// What drives an axis made by `bind_axis_keys` and `bind_axis_gamepad`
+#[derive(Clone, Default, Debug)]
+pub(crate) struct AxisBindings {
+    /// Pulls the axis to -1 while held
+    pub(crate) negative: Vec<InputBinding>,
+    /// Pushes the axis to 1 while held
+    pub(crate) positive: Vec<InputBinding>,
+    /// Read from every connected gamepad
+    pub(crate) gamepad: Vec<GamepadAxis>,
+}
+
+/// A gamepad stick or trigger
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum GamepadAxis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+impl From<GamepadAxis> for sdl2::controller::Axis {
+    fn from(axis: GamepadAxis) -> Self {
+        match axis {
+            GamepadAxis::LeftX => sdl2::controller::Axis::LeftX,
+            GamepadAxis::LeftY => sdl2::controller::Axis::LeftY,
+            GamepadAxis::RightX => sdl2::controller::Axis::RightX,
+            GamepadAxis::RightY => sdl2::controller::Axis::RightY,
+            GamepadAxis::LeftTrigger => sdl2::controller::Axis::TriggerLeft,
+            GamepadAxis::RightTrigger => sdl2::controller::Axis::TriggerRight,
+        }
+    }
+}
+impl From<sdl2::controller::Axis> for GamepadAxis {
+    fn from(axis: sdl2::controller::Axis) -> Self {
+        match axis {
+            sdl2::controller::Axis::LeftX => GamepadAxis::LeftX,
+            sdl2::controller::Axis::LeftY => GamepadAxis::LeftY,
+            sdl2::controller::Axis::RightX => GamepadAxis::RightX,
+            sdl2::controller::Axis::RightY => GamepadAxis::RightY,
+            sdl2::controller::Axis::TriggerLeft => GamepadAxis::LeftTrigger,
+            sdl2::controller::Axis::TriggerRight => GamepadAxis::RightTrigger,
+        }
+    }
+}
+
+/// A finger on a touchscreen
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TouchPoint {
+    /// Stays the same while the finger is down
+    pub id: i64,
+    /// Position in window coordinates
+    pub x: f32,
+    pub y: f32,
+}
+
+/// A touch gesture, see `touch::get_gesture_detected`
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub enum Gesture {
+    #[default]
+    None,
+    /// A quick touch and release. Lasts one frame
+    Tap,
+    /// Two taps in the same place. Lasts one frame
+    DoubleTap,
+    /// A finger held still. Lasts until it lifts or moves
+    Hold,
+    /// A finger moving. Lasts until it lifts
+    Drag,
+    /// A fast drag that was released. Lasts one frame
+    SwipeRight,
+    SwipeLeft,
+    SwipeUp,
+    SwipeDown,
+    /// Two fingers moving together. Lasts until one lifts
+    PinchIn,
+    /// Two fingers moving apart. Lasts until one lifts
+    PinchOut,
+}
+
+/// A cursor shape the OS provides
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SystemCursor {
+    Arrow,
+    /// For text that can be selected or edited
+    IBeam,
+    Wait,
+    /// An arrow with a small spinner, for when work happens in the background
+    WaitArrow,
+    Crosshair,
+    /// For resizing from the top left or bottom right corner
+    ResizeNWSE,
+    /// For resizing from the top right or bottom left corner
+    ResizeNESW,
+    /// For resizing horizontally
+    ResizeEW,
+    /// For resizing vertically
+    ResizeNS,
+    /// For moving things
+    ResizeAll,
+    NotAllowed,
+    /// For links and buttons
+    Hand,
+}
+impl From<SystemCursor> for sdl2::mouse::SystemCursor {
+    fn from(cursor: SystemCursor) -> Self {
+        match cursor {
+            SystemCursor::Arrow => sdl2::mouse::SystemCursor::Arrow,
+            SystemCursor::IBeam => sdl2::mouse::SystemCursor::IBeam,
+            SystemCursor::Wait => sdl2::mouse::SystemCursor::Wait,
+            SystemCursor::WaitArrow => sdl2::mouse::SystemCursor::WaitArrow,
+            SystemCursor::Crosshair => sdl2::mouse::SystemCursor::Crosshair,
+            SystemCursor::ResizeNWSE => sdl2::mouse::SystemCursor::SizeNWSE,
+            SystemCursor::ResizeNESW => sdl2::mouse::SystemCursor::SizeNESW,
+            SystemCursor::ResizeEW => sdl2::mouse::SystemCursor::SizeWE,
+            SystemCursor::ResizeNS => sdl2::mouse::SystemCursor::SizeNS,
+            SystemCursor::ResizeAll => sdl2::mouse::SystemCursor::SizeAll,
+            SystemCursor::NotAllowed => sdl2::mouse::SystemCursor::No,
+            SystemCursor::Hand => sdl2::mouse::SystemCursor::Hand,
         }
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
 pub struct Color {
     r: u8,
     g: u8,
@@ -182,6 +492,219 @@ impl From<Color> for pixels::Color {
     }
 }
 
+/// A rectangle in screen coordinates
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Rectangle {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+impl From<sdl2::rect::Rect> for Rectangle {
+    fn from(value: sdl2::rect::Rect) -> Self {
+        Rectangle {
+            x: value.x(),
+            y: value.y(),
+            width: value.width(),
+            height: value.height(),
+        }
+    }
+}
+
+/// Text an IME is still composing, before it is committed as typed characters
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub struct TextComposition {
+    /// The text being composed
+    pub text: String,
+    /// The cursor position in the text, in characters
+    pub cursor: usize,
+    /// How many characters after the cursor are selected
+    pub selection_length: usize,
+}
+
+/// A resolution and refresh rate a monitor supports
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct DisplayMode {
+    pub width: i32,
+    pub height: i32,
+    pub refresh_rate: i32,
+}
+impl From<video::DisplayMode> for DisplayMode {
+    fn from(value: video::DisplayMode) -> Self {
+        DisplayMode {
+            width: value.w,
+            height: value.h,
+            refresh_rate: value.refresh_rate,
+        }
+    }
+}
+
+/// Something that happened this frame, from `poll_events`
+#[derive(Clone, PartialEq, Debug)]
+pub enum Event {
+    /// Something happened to a window itself
+    Window { window: WindowHandle, event: WindowEvent },
+    /// A key went down, or the OS repeated it while held
+    KeyDown { window: WindowHandle, key: Scancode, keycode: Keycode, repeat: bool },
+    KeyUp { window: WindowHandle, key: Scancode, keycode: Keycode },
+    /// Text was typed while text input was on, see `start_text_input`
+    TextInput { window: WindowHandle, text: String },
+    /// The mouse moved to `x`, `y`, by `dx`, `dy`
+    MouseMotion { window: WindowHandle, x: i32, y: i32, dx: i32, dy: i32 },
+    MouseButtonDown { window: WindowHandle, button: MouseButton, x: i32, y: i32, clicks: u8 },
+    MouseButtonUp { window: WindowHandle, button: MouseButton, x: i32, y: i32 },
+    /// The mouse wheel moved, with up and right positive
+    MouseWheel { window: WindowHandle, x: f32, y: f32 },
+    FileDropped { window: WindowHandle, path: PathBuf },
+    TextDropped { window: WindowHandle, text: String },
+    /// A gamepad connected in this slot
+    GamepadConnected { gamepad: usize },
+    GamepadDisconnected { gamepad: usize },
+    GamepadButtonDown { gamepad: usize, button: GamepadButton },
+    GamepadButtonUp { gamepad: usize, button: GamepadButton },
+    /// A stick or trigger moved. The value doesn't have the deadzone applied
+    GamepadAxisMotion { gamepad: usize, axis: GamepadAxis, value: f32 },
+    /// A finger touched the screen, in pixels inside the window
+    TouchDown { window: WindowHandle, point: TouchPoint },
+    TouchUp { window: WindowHandle, point: TouchPoint },
+    TouchMotion { window: WindowHandle, point: TouchPoint },
+    /// Something new was copied, in this app or another one
+    ClipboardUpdated,
+    /// The OS asked the whole app to quit
+    Quit,
+}
+
+/// What happened to a window in `Event::Window`
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum WindowEvent {
+    Shown,
+    Hidden,
+    /// The window moved to this position on the desktop
+    Moved(i32, i32),
+    /// The window is now this size
+    Resized(u32, u32),
+    Minimized,
+    Maximized,
+    Restored,
+    /// The mouse moved onto the window
+    MouseEntered,
+    /// The mouse moved off the window
+    MouseLeft,
+    FocusGained,
+    FocusLost,
+    /// The close button was clicked. See `on_close_requested` to stop it closing
+    CloseRequested,
+}
+
+/// A handle to a window opened with `init_window`
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct WindowHandle {
+    /// The SDL window id
+    pub(crate) id: u32,
+}
+impl WindowHandle {
+    /// Creates a handle from an SDL window id
+    pub fn from_id(id: u32) -> WindowHandle {
+        WindowHandle { id }
+    }
+
+    /// The SDL window id behind this handle
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+}
+
+/// Lets code written for window ids keep passing them, like `switch_window(id)`
+impl From<u32> for WindowHandle {
+    fn from(id: u32) -> Self {
+        WindowHandle::from_id(id)
+    }
+}
+
+/// How `begin_drawing` paces frames
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum FrameLimit {
+    /// Sleep between frames to run at this many frames per second
+    Fps(u32),
+    /// Run frames as fast as possible
+    Unlimited,
+    /// Let presenting the canvas wait for the display. SDL waits at the display's refresh rate itself
+    /// for renderers that can't vsync
+    Vsync,
+}
+
+/// What the gesture detector remembers between touch events
+#[derive(Default)]
+pub(crate) struct GestureState {
+    pub(crate) current: Gesture,
+    /// When and where the first finger went down
+    pub(crate) start_time: f64,
+    pub(crate) start: (f32, f32),
+    /// Whether the finger has moved too far to count as a tap or hold
+    pub(crate) moved: bool,
+    /// When and where the last tap was, for double taps
+    pub(crate) last_tap_time: Option<f64>,
+    pub(crate) last_tap: (f32, f32),
+    /// Set once a second finger goes down, so lifting doesn't count as a tap
+    pub(crate) multi_touch: bool,
+    pub(crate) drag_vector: (f32, f32),
+    pub(crate) pinch_start_distance: f32,
+    pub(crate) pinch_scale: f32,
+}
+
+/// Input that SDL sent to one window
+#[derive(Default)]
+pub(crate) struct WindowInput {
+    /// Keys held down while the window has focus
+    pub(crate) keys_down: HashSet<Scancode>,
+    /// Keys that went down this frame, oldest first, for `get_key_pressed`
+    pub(crate) key_queue: VecDeque<Scancode>,
+    /// Keys that were held down last frame
+    pub(crate) previous_keys_down: HashSet<Scancode>,
+    /// Keys the OS sent a repeat for this frame
+    pub(crate) keys_repeated: HashSet<Scancode>,
+    /// Whether typed characters are collected for this window
+    pub(crate) text_input: bool,
+    /// Characters typed this frame, oldest first, for `get_char_pressed`
+    pub(crate) char_queue: VecDeque<char>,
+    /// Text the IME is composing, if any
+    pub(crate) composition: Option<TextComposition>,
+    /// Mouse buttons held down over the window
+    pub(crate) buttons_down: HashSet<sdl2::mouse::MouseButton>,
+    /// Mouse buttons that were held down last frame
+    pub(crate) previous_buttons_down: HashSet<sdl2::mouse::MouseButton>,
+    /// Mouse x relative to the window
+    pub(crate) mouse_x: i32,
+    /// Mouse y relative to the window
+    pub(crate) mouse_y: i32,
+    /// How far the mouse moved this frame
+    pub(crate) mouse_delta: (i32, i32),
+    /// How far the wheel scrolled this frame, horizontally and vertically
+    pub(crate) wheel_move: (f32, f32),
+    /// Files dropped on the window that haven't been loaded yet
+    pub(crate) dropped_files: Vec<PathBuf>,
+    /// Text dropped on the window that hasn't been loaded yet
+    pub(crate) dropped_text: Vec<String>,
+    /// Set between SDL's drop begin and drop complete events
+    pub(crate) drop_in_progress: bool,
+    /// Fingers on the touchscreen, in the order they went down
+    pub(crate) touch_points: Vec<TouchPoint>,
+    pub(crate) gestures: GestureState,
+}
+
+/// A connected gamepad and its input
+pub(crate) struct Gamepad {
+    pub(crate) controller: GameController,
+    /// Buttons held down
+    pub(crate) buttons_down: HashSet<GamepadButton>,
+    /// Buttons that were held down last frame
+    pub(crate) previous_buttons_down: HashSet<GamepadButton>,
+    /// Where each stick and trigger is, from -1 to 1, before the deadzone
+    pub(crate) axes: HashMap<GamepadAxis, f32>,
+    /// Made by `attach_virtual_gamepad` or playback, so maylib can unplug it
+    pub(crate) is_virtual: bool,
+}
+
 pub(crate) struct Window {
     /// The actual window
     pub(crate) window: video::Window,
@@ -205,14 +728,62 @@ pub(crate) struct Window {
     pub(crate) focused: bool,
     /// Is window bordered
     pub(crate) bordered: bool,
-    /// Has window been resized
+    /// Has window been resized this frame
     pub(crate) resized: bool,
+    /// Has window been moved this frame
+    pub(crate) moved: bool,
+    /// Is the mouse over the window
+    pub(crate) hovered: bool,
     /// Previous frame time
     pub(crate) previous_time: f64,
     /// Current time
     pub(crate) current_time: f64,
     /// Time window was opened
     pub(crate) start_time: f64,
+    /// Frames drawn since the window was opened
+    pub(crate) frame_count: u64,
+    /// Smoothed frame time, used for fps
+    pub(crate) average_frame_time: f64,
+    /// Can veto close requests
+    pub(crate) close_requested_callback: Option<CloseRequestedCallback>,
+    /// Input sent to this window
+    pub(crate) input: WindowInput,
+    /// The cursor shown while the mouse is over this window, `None` for the default arrow
+    pub(crate) cursor: Option<Cursor>,
+    /// Asked for vsync in its `WindowBuilder`, so it keeps vsync whatever the frame limit is
+    pub(crate) vsync: bool,
+}
+impl Window {
+    /// Tear the window down in order: textures, then the canvas, then the window itself
+    pub(crate) fn destroy(self) {
+        let Window { window, canvas, texture, .. } = self;
+        drop(texture);
+        drop(canvas);
+        drop(window);
+    }
+}
+
+/// Input being written to a file by `start_recording`
+pub(crate) struct Recording {
+    pub(crate) writer: BufWriter<File>,
+    /// The frame time of the last recorded frame, so each frame stores how long it took
+    pub(crate) last_time: f64,
+    /// A write that failed mid-recording, returned by `stop_recording`
+    pub(crate) error: Option<std::io::Error>,
+    /// Events encoded since the last frame, written with the next one
+    pub(crate) pending: Vec<Vec<u8>>,
+}
+
+/// A recording being replayed by `start_playback`
+pub(crate) struct Playback {
+    /// How long each frame took and what happened in it, oldest first
+    pub(crate) frames: VecDeque<(f64, Vec<sdl2::event::Event>)>,
+    /// The recorded clock, which stands in for the real one
+    pub(crate) time: f64,
+    /// The gamepads from before playback, put back when it ends
+    pub(crate) live_gamepads: Vec<Option<Gamepad>>,
+    /// The slot each virtual gamepad standing in for a recorded one goes in, by SDL device index
+    pub(crate) slots: HashMap<u32, usize>,
 }
 
 pub struct Maylib {
@@ -230,74 +801,162 @@ pub struct Maylib {
     pub(crate) current_window: u32,
     /// all windows
     pub(crate) windows: HashMap<u32, Window>,
-    /// The frame rate to run at
-    pub(crate) frame_rate: i32,
-    /// The delay between each frame
-    pub(crate) frame_time: f32,
+    /// How frames are paced
+    pub(crate) frame_limit: FrameLimit,
+    /// When the current frame started, used to pace the next one
+    pub(crate) frame_start: Option<Instant>,
+    /// The performance counter when maylib started
+    start_counter: u64,
+    /// Frames left before every window is asked to close
+    pub(crate) frames_left: Option<u64>,
+    /// Running without a display
+    pub(crate) headless: bool,
+    /// Has SDL asked the whole app to quit
+    pub(crate) quit_requested: bool,
+    /// Run by `close_maylib`, in the order they were added
+    pub(crate) shutdown_hooks: Vec<Box<dyn FnOnce() + Send>>,
+    /// Run after any window closes
+    pub(crate) window_closed_callbacks: Vec<WindowClosedCallback>,
+    /// `None` if SDL couldn't start gamepad support
+    pub(crate) game_controller: Option<GameControllerSubsystem>,
+    /// Connected gamepads by slot. Slots are reused after a gamepad disconnects
+    pub(crate) gamepads: Vec<Option<Gamepad>>,
+    /// Stick and trigger movement smaller than this reads as 0
+    pub(crate) gamepad_deadzone: f32,
+    pub(crate) gamepad_connected_callbacks: Vec<GamepadCallback>,
+    pub(crate) gamepad_disconnected_callbacks: Vec<GamepadCallback>,
+    /// Virtual gamepads attached or detached since the last frame, announced by the next `begin_drawing`
+    pub(crate) gamepad_hotplugs: Vec<GamepadHotplug>,
+    /// Shown over windows that have no cursor of their own
+    pub(crate) default_cursor: Option<Cursor>,
+    /// Where input is being recorded to, if anywhere
+    pub(crate) recording: Option<Recording>,
+    /// The recording replacing live input, if one is playing
+    pub(crate) playback: Option<Playback>,
+    /// Named actions and what triggers them
+    pub(crate) actions: HashMap<String, Vec<InputBinding>>,
+    /// Named axes and what drives them
+    pub(crate) axes: HashMap<String, AxisBindings>,
+    /// Set by `listen_for_input` until something is pressed
+    pub(crate) listening_for_input: bool,
+    /// What was pressed while listening, until `get_listened_input` takes it
+    pub(crate) listened_input: Option<InputBinding>,
+    /// This frame's events, until `poll_events` takes them
+    pub(crate) events: VecDeque<Event>,
     /// The audio stream. Unused, but needs to stay loaded
-    _audio_stream: Option<OutputStream>,
+    pub(crate) _audio_stream: Option<OutputStream>,
     /// The audio stream handle
     pub(crate) audio: Option<OutputStreamHandle>,
 }
 unsafe impl Send for Maylib {}
 impl Maylib {
     /// Initialize maylib
-    pub fn init() -> Result<Maylib, String> {
-        let sdl: Sdl = match sdl2::init() {
-            Ok(s) => s,
-            Err(e) => {
-                return Err(e);
-            }
-        };
-        let video: VideoSubsystem = match sdl.video() {
-            Ok(v) => v,
-            Err(e) => {
-                return Err(e);
-            }
-        };
-        let event_pump: EventPump = match sdl.event_pump() {
-            Ok(p) => p,
-            Err(e) => {
-                return Err(e);
-            }
-        };
+    pub fn init() -> Result<Maylib, Error> {
+        Maylib::init_with(false)
+    }
+
+    /// Initialize maylib without a display or audio device, for CI
+    pub fn init_headless() -> Result<Maylib, Error> {
+        Maylib::init_with(true)
+    }
+
+    fn init_with(headless: bool) -> Result<Maylib, Error> {
+        // Closing the last window is a close request like any other, not a quit.
+        // Quit then only comes from the OS or Ctrl+C/SIGTERM
+        sdl2::hint::set("SDL_QUIT_ON_LAST_WINDOW_CLOSE", "0");
+        if headless {
+            // Must be set before the video subsystem starts
+            sdl2::hint::set_with_priority("SDL_VIDEODRIVER", "offscreen,dummy", &Hint::Override);
+            sdl2::hint::set_with_priority("SDL_RENDER_DRIVER", "software", &Hint::Override);
+        }
+        let sdl: Sdl = sdl2::init().map_err(Error::Sdl)?;
+        let video: VideoSubsystem = sdl.video().map_err(Error::Sdl)?;
+        let event_pump: EventPump = sdl.event_pump().map_err(Error::Sdl)?;
         let clipboard: ClipboardUtil = video.clipboard();
+        // SDL starts with text input on, which would pop up the IME before anyone asked for it
+        video.text_input().stop();
         let mouse: MouseUtil = sdl.mouse();
-        let timer: TimerSubsystem = match sdl.timer() {
-            Ok(t) => t,
-            Err(e) => {
-                return Err(e);
-            }
-        };
+        let timer: TimerSubsystem = sdl.timer().map_err(Error::Sdl)?;
+        // Gamepads are optional, SDL sends an added event for each one already plugged in
+        let game_controller = sdl.game_controller().ok();
         sdl2::image::init(InitFlag::PNG | InitFlag::JPG | InitFlag::TIF | InitFlag::WEBP)
-            .expect("Image should init successfully");
+            .map_err(Error::Sdl)?;
+        // Headless runs get no audio stream, so sounds are dropped
         let (_aud, aud)  = match OutputStream::try_default() {
-            Ok(s) => {
+            Ok(s) if !headless => {
                 (Some(s.0), Some(s.1))
             },
-            Err(e) => {
+            _ => {
                 (None, None)
             }
         };
+        let start_counter = timer.performance_counter();
         Ok(Maylib {
             video,
             event_pump,
             clipboard,
             mouse,
             timer,
-            current_window: 4294967295,
+            current_window: NO_WINDOW,
             windows: HashMap::new(),
-            frame_rate: 60,
-            frame_time: 1.0 / 60f32,
+            frame_limit: if headless { FrameLimit::Unlimited } else { FrameLimit::Fps(60) },
+            frame_start: None,
+            frames_left: None,
+            headless,
+            quit_requested: false,
+            shutdown_hooks: Vec::new(),
+            window_closed_callbacks: Vec::new(),
+            start_counter,
+            game_controller,
+            gamepads: Vec::new(),
+            gamepad_deadzone: 0.1,
+            gamepad_connected_callbacks: Vec::new(),
+            gamepad_disconnected_callbacks: Vec::new(),
+            gamepad_hotplugs: Vec::new(),
+            // Video drivers without a cursor, like the headless ones, can't make one
+            default_cursor: Cursor::from_system(sdl2::mouse::SystemCursor::Arrow).ok(),
+            recording: None,
+            playback: None,
+            actions: HashMap::new(),
+            axes: HashMap::new(),
+            listening_for_input: false,
+            listened_input: None,
+            events: VecDeque::new(),
             _audio_stream: _aud,
             audio: aud,
         })
     }
+
+    /// Seconds since maylib started, from SDL's high resolution counter
+    /// While a recording plays back, this is the recorded time instead
+    pub(crate) fn time(&self) -> f64 {
+        if let Some(playback) = &self.playback {
+            return playback.time;
+        }
+        let elapsed = self.timer.performance_counter() - self.start_counter;
+        elapsed as f64 / self.timer.performance_frequency() as f64
+    }
+
+    /// Get a window by its handle
+    pub(crate) fn window(&self, handle: WindowHandle) -> Result<&Window, Error> {
+        self.windows
+            .get(&handle.id)
+            .ok_or(Error::InvalidWindow(handle.id))
+    }
+
+    /// Get a window by its handle, mutably
+    pub(crate) fn window_mut(&mut self, handle: WindowHandle) -> Result<&mut Window, Error> {
+        self.windows
+            .get_mut(&handle.id)
+            .ok_or(Error::InvalidWindow(handle.id))
+    }
 }
 
+/// A physical key, named after where it sits on a US keyboard no matter the layout
 #[repr(i32)]
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-pub enum Keycode {
+pub enum Scancode {
+    Unknown = SDL_Scancode::SDL_SCANCODE_UNKNOWN as i32,
     A = SDL_Scancode::SDL_SCANCODE_A as i32,
     B = SDL_Scancode::SDL_SCANCODE_B as i32,
     C = SDL_Scancode::SDL_SCANCODE_C as i32,
@@ -364,6 +1023,40 @@ pub enum Keycode {
     F10 = SDL_Scancode::SDL_SCANCODE_F10 as i32,
     F11 = SDL_Scancode::SDL_SCANCODE_F11 as i32,
     F12 = SDL_Scancode::SDL_SCANCODE_F12 as i32,
+    PrintScreen = SDL_Scancode::SDL_SCANCODE_PRINTSCREEN as i32,
+    ScrollLock = SDL_Scancode::SDL_SCANCODE_SCROLLLOCK as i32,
+    Pause = SDL_Scancode::SDL_SCANCODE_PAUSE as i32,
+    Insert = SDL_Scancode::SDL_SCANCODE_INSERT as i32,
+    Home = SDL_Scancode::SDL_SCANCODE_HOME as i32,
+    PageUp = SDL_Scancode::SDL_SCANCODE_PAGEUP as i32,
+    Delete = SDL_Scancode::SDL_SCANCODE_DELETE as i32,
+    End = SDL_Scancode::SDL_SCANCODE_END as i32,
+    PageDown = SDL_Scancode::SDL_SCANCODE_PAGEDOWN as i32,
+    Right = SDL_Scancode::SDL_SCANCODE_RIGHT as i32,
+    Left = SDL_Scancode::SDL_SCANCODE_LEFT as i32,
+    Down = SDL_Scancode::SDL_SCANCODE_DOWN as i32,
+    Up = SDL_Scancode::SDL_SCANCODE_UP as i32,
+    NumLockClear = SDL_Scancode::SDL_SCANCODE_NUMLOCKCLEAR as i32,
+    KpDivide = SDL_Scancode::SDL_SCANCODE_KP_DIVIDE as i32,
+    KpMultiply = SDL_Scancode::SDL_SCANCODE_KP_MULTIPLY as i32,
+    KpMinus = SDL_Scancode::SDL_SCANCODE_KP_MINUS as i32,
+    KpPlus = SDL_Scancode::SDL_SCANCODE_KP_PLUS as i32,
+    KpEnter = SDL_Scancode::SDL_SCANCODE_KP_ENTER as i32,
+    Kp1 = SDL_Scancode::SDL_SCANCODE_KP_1 as i32,
+    Kp2 = SDL_Scancode::SDL_SCANCODE_KP_2 as i32,
+    Kp3 = SDL_Scancode::SDL_SCANCODE_KP_3 as i32,
+    Kp4 = SDL_Scancode::SDL_SCANCODE_KP_4 as i32,
+    Kp5 = SDL_Scancode::SDL_SCANCODE_KP_5 as i32,
+    Kp6 = SDL_Scancode::SDL_SCANCODE_KP_6 as i32,
+    Kp7 = SDL_Scancode::SDL_SCANCODE_KP_7 as i32,
+    Kp8 = SDL_Scancode::SDL_SCANCODE_KP_8 as i32,
+    Kp9 = SDL_Scancode::SDL_SCANCODE_KP_9 as i32,
+    Kp0 = SDL_Scancode::SDL_SCANCODE_KP_0 as i32,
+    KpPeriod = SDL_Scancode::SDL_SCANCODE_KP_PERIOD as i32,
+    NonUsBackslash = SDL_Scancode::SDL_SCANCODE_NONUSBACKSLASH as i32,
+    Application = SDL_Scancode::SDL_SCANCODE_APPLICATION as i32,
+    Power = SDL_Scancode::SDL_SCANCODE_POWER as i32,
+    KpEquals = SDL_Scancode::SDL_SCANCODE_KP_EQUALS as i32,
     F13 = SDL_Scancode::SDL_SCANCODE_F13 as i32,
     F14 = SDL_Scancode::SDL_SCANCODE_F14 as i32,
     F15 = SDL_Scancode::SDL_SCANCODE_F15 as i32,
@@ -376,98 +1069,995 @@ pub enum Keycode {
     F22 = SDL_Scancode::SDL_SCANCODE_F22 as i32,
     F23 = SDL_Scancode::SDL_SCANCODE_F23 as i32,
     F24 = SDL_Scancode::SDL_SCANCODE_F24 as i32,
-    Delete = SDL_Scancode::SDL_SCANCODE_DELETE as i32,
-    Right = SDL_Scancode::SDL_SCANCODE_RIGHT as i32,
-    Left = SDL_Scancode::SDL_SCANCODE_LEFT as i32,
-    Down = SDL_Scancode::SDL_SCANCODE_DOWN as i32,
-    Up = SDL_Scancode::SDL_SCANCODE_UP as i32,
+    Execute = SDL_Scancode::SDL_SCANCODE_EXECUTE as i32,
+    Help = SDL_Scancode::SDL_SCANCODE_HELP as i32,
+    Menu = SDL_Scancode::SDL_SCANCODE_MENU as i32,
+    Select = SDL_Scancode::SDL_SCANCODE_SELECT as i32,
+    Stop = SDL_Scancode::SDL_SCANCODE_STOP as i32,
+    Again = SDL_Scancode::SDL_SCANCODE_AGAIN as i32,
+    Undo = SDL_Scancode::SDL_SCANCODE_UNDO as i32,
+    Cut = SDL_Scancode::SDL_SCANCODE_CUT as i32,
+    Copy = SDL_Scancode::SDL_SCANCODE_COPY as i32,
+    Paste = SDL_Scancode::SDL_SCANCODE_PASTE as i32,
+    Find = SDL_Scancode::SDL_SCANCODE_FIND as i32,
+    Mute = SDL_Scancode::SDL_SCANCODE_MUTE as i32,
+    VolumeUp = SDL_Scancode::SDL_SCANCODE_VOLUMEUP as i32,
+    VolumeDown = SDL_Scancode::SDL_SCANCODE_VOLUMEDOWN as i32,
+    KpComma = SDL_Scancode::SDL_SCANCODE_KP_COMMA as i32,
+    KpEqualsAS400 = SDL_Scancode::SDL_SCANCODE_KP_EQUALSAS400 as i32,
+    International1 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL1 as i32,
+    International2 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL2 as i32,
+    International3 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL3 as i32,
+    International4 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL4 as i32,
+    International5 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL5 as i32,
+    International6 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL6 as i32,
+    International7 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL7 as i32,
+    International8 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL8 as i32,
+    International9 = SDL_Scancode::SDL_SCANCODE_INTERNATIONAL9 as i32,
+    Lang1 = SDL_Scancode::SDL_SCANCODE_LANG1 as i32,
+    Lang2 = SDL_Scancode::SDL_SCANCODE_LANG2 as i32,
+    Lang3 = SDL_Scancode::SDL_SCANCODE_LANG3 as i32,
+    Lang4 = SDL_Scancode::SDL_SCANCODE_LANG4 as i32,
+    Lang5 = SDL_Scancode::SDL_SCANCODE_LANG5 as i32,
+    Lang6 = SDL_Scancode::SDL_SCANCODE_LANG6 as i32,
+    Lang7 = SDL_Scancode::SDL_SCANCODE_LANG7 as i32,
+    Lang8 = SDL_Scancode::SDL_SCANCODE_LANG8 as i32,
+    Lang9 = SDL_Scancode::SDL_SCANCODE_LANG9 as i32,
+    AltErase = SDL_Scancode::SDL_SCANCODE_ALTERASE as i32,
+    SysReq = SDL_Scancode::SDL_SCANCODE_SYSREQ as i32,
+    Cancel = SDL_Scancode::SDL_SCANCODE_CANCEL as i32,
+    Clear = SDL_Scancode::SDL_SCANCODE_CLEAR as i32,
+    Prior = SDL_Scancode::SDL_SCANCODE_PRIOR as i32,
+    Return2 = SDL_Scancode::SDL_SCANCODE_RETURN2 as i32,
+    Separator = SDL_Scancode::SDL_SCANCODE_SEPARATOR as i32,
+    Out = SDL_Scancode::SDL_SCANCODE_OUT as i32,
+    Oper = SDL_Scancode::SDL_SCANCODE_OPER as i32,
+    ClearAgain = SDL_Scancode::SDL_SCANCODE_CLEARAGAIN as i32,
+    CrSel = SDL_Scancode::SDL_SCANCODE_CRSEL as i32,
+    ExSel = SDL_Scancode::SDL_SCANCODE_EXSEL as i32,
+    Kp00 = SDL_Scancode::SDL_SCANCODE_KP_00 as i32,
+    Kp000 = SDL_Scancode::SDL_SCANCODE_KP_000 as i32,
+    ThousandsSeparator = SDL_Scancode::SDL_SCANCODE_THOUSANDSSEPARATOR as i32,
+    DecimalSeparator = SDL_Scancode::SDL_SCANCODE_DECIMALSEPARATOR as i32,
+    CurrencyUnit = SDL_Scancode::SDL_SCANCODE_CURRENCYUNIT as i32,
+    CurrencySubUnit = SDL_Scancode::SDL_SCANCODE_CURRENCYSUBUNIT as i32,
+    KpLeftParen = SDL_Scancode::SDL_SCANCODE_KP_LEFTPAREN as i32,
+    KpRightParen = SDL_Scancode::SDL_SCANCODE_KP_RIGHTPAREN as i32,
+    KpLeftBrace = SDL_Scancode::SDL_SCANCODE_KP_LEFTBRACE as i32,
+    KpRightBrace = SDL_Scancode::SDL_SCANCODE_KP_RIGHTBRACE as i32,
+    KpTab = SDL_Scancode::SDL_SCANCODE_KP_TAB as i32,
+    KpBackspace = SDL_Scancode::SDL_SCANCODE_KP_BACKSPACE as i32,
+    KpA = SDL_Scancode::SDL_SCANCODE_KP_A as i32,
+    KpB = SDL_Scancode::SDL_SCANCODE_KP_B as i32,
+    KpC = SDL_Scancode::SDL_SCANCODE_KP_C as i32,
+    KpD = SDL_Scancode::SDL_SCANCODE_KP_D as i32,
+    KpE = SDL_Scancode::SDL_SCANCODE_KP_E as i32,
+    KpF = SDL_Scancode::SDL_SCANCODE_KP_F as i32,
+    KpXor = SDL_Scancode::SDL_SCANCODE_KP_XOR as i32,
+    KpPower = SDL_Scancode::SDL_SCANCODE_KP_POWER as i32,
+    KpPercent = SDL_Scancode::SDL_SCANCODE_KP_PERCENT as i32,
+    KpLess = SDL_Scancode::SDL_SCANCODE_KP_LESS as i32,
+    KpGreater = SDL_Scancode::SDL_SCANCODE_KP_GREATER as i32,
+    KpAmpersand = SDL_Scancode::SDL_SCANCODE_KP_AMPERSAND as i32,
+    KpDblAmpersand = SDL_Scancode::SDL_SCANCODE_KP_DBLAMPERSAND as i32,
+    KpVerticalBar = SDL_Scancode::SDL_SCANCODE_KP_VERTICALBAR as i32,
+    KpDblVerticalBar = SDL_Scancode::SDL_SCANCODE_KP_DBLVERTICALBAR as i32,
+    KpColon = SDL_Scancode::SDL_SCANCODE_KP_COLON as i32,
+    KpHash = SDL_Scancode::SDL_SCANCODE_KP_HASH as i32,
+    KpSpace = SDL_Scancode::SDL_SCANCODE_KP_SPACE as i32,
+    KpAt = SDL_Scancode::SDL_SCANCODE_KP_AT as i32,
+    KpExclam = SDL_Scancode::SDL_SCANCODE_KP_EXCLAM as i32,
+    KpMemStore = SDL_Scancode::SDL_SCANCODE_KP_MEMSTORE as i32,
+    KpMemRecall = SDL_Scancode::SDL_SCANCODE_KP_MEMRECALL as i32,
+    KpMemClear = SDL_Scancode::SDL_SCANCODE_KP_MEMCLEAR as i32,
+    KpMemAdd = SDL_Scancode::SDL_SCANCODE_KP_MEMADD as i32,
+    KpMemSubtract = SDL_Scancode::SDL_SCANCODE_KP_MEMSUBTRACT as i32,
+    KpMemMultiply = SDL_Scancode::SDL_SCANCODE_KP_MEMMULTIPLY as i32,
+    KpMemDivide = SDL_Scancode::SDL_SCANCODE_KP_MEMDIVIDE as i32,
+    KpPlusMinus = SDL_Scancode::SDL_SCANCODE_KP_PLUSMINUS as i32,
+    KpClear = SDL_Scancode::SDL_SCANCODE_KP_CLEAR as i32,
+    KpClearEntry = SDL_Scancode::SDL_SCANCODE_KP_CLEARENTRY as i32,
+    KpBinary = SDL_Scancode::SDL_SCANCODE_KP_BINARY as i32,
+    KpOctal = SDL_Scancode::SDL_SCANCODE_KP_OCTAL as i32,
+    KpDecimal = SDL_Scancode::SDL_SCANCODE_KP_DECIMAL as i32,
+    KpHexadecimal = SDL_Scancode::SDL_SCANCODE_KP_HEXADECIMAL as i32,
+    LCtrl = SDL_Scancode::SDL_SCANCODE_LCTRL as i32,
+    LShift = SDL_Scancode::SDL_SCANCODE_LSHIFT as i32,
+    LAlt = SDL_Scancode::SDL_SCANCODE_LALT as i32,
+    LGui = SDL_Scancode::SDL_SCANCODE_LGUI as i32,
+    RCtrl = SDL_Scancode::SDL_SCANCODE_RCTRL as i32,
+    RShift = SDL_Scancode::SDL_SCANCODE_RSHIFT as i32,
+    RAlt = SDL_Scancode::SDL_SCANCODE_RALT as i32,
+    RGui = SDL_Scancode::SDL_SCANCODE_RGUI as i32,
+    Mode = SDL_Scancode::SDL_SCANCODE_MODE as i32,
+    AudioNext = SDL_Scancode::SDL_SCANCODE_AUDIONEXT as i32,
+    AudioPrev = SDL_Scancode::SDL_SCANCODE_AUDIOPREV as i32,
+    AudioStop = SDL_Scancode::SDL_SCANCODE_AUDIOSTOP as i32,
+    AudioPlay = SDL_Scancode::SDL_SCANCODE_AUDIOPLAY as i32,
+    AudioMute = SDL_Scancode::SDL_SCANCODE_AUDIOMUTE as i32,
+    MediaSelect = SDL_Scancode::SDL_SCANCODE_MEDIASELECT as i32,
+    Www = SDL_Scancode::SDL_SCANCODE_WWW as i32,
+    Mail = SDL_Scancode::SDL_SCANCODE_MAIL as i32,
+    Calculator = SDL_Scancode::SDL_SCANCODE_CALCULATOR as i32,
+    Computer = SDL_Scancode::SDL_SCANCODE_COMPUTER as i32,
+    AcSearch = SDL_Scancode::SDL_SCANCODE_AC_SEARCH as i32,
+    AcHome = SDL_Scancode::SDL_SCANCODE_AC_HOME as i32,
+    AcBack = SDL_Scancode::SDL_SCANCODE_AC_BACK as i32,
+    AcForward = SDL_Scancode::SDL_SCANCODE_AC_FORWARD as i32,
+    AcStop = SDL_Scancode::SDL_SCANCODE_AC_STOP as i32,
+    AcRefresh = SDL_Scancode::SDL_SCANCODE_AC_REFRESH as i32,
+    AcBookmarks = SDL_Scancode::SDL_SCANCODE_AC_BOOKMARKS as i32,
+    BrightnessDown = SDL_Scancode::SDL_SCANCODE_BRIGHTNESSDOWN as i32,
+    BrightnessUp = SDL_Scancode::SDL_SCANCODE_BRIGHTNESSUP as i32,
+    DisplaySwitch = SDL_Scancode::SDL_SCANCODE_DISPLAYSWITCH as i32,
+    KbdIllumToggle = SDL_Scancode::SDL_SCANCODE_KBDILLUMTOGGLE as i32,
+    KbdIllumDown = SDL_Scancode::SDL_SCANCODE_KBDILLUMDOWN as i32,
+    KbdIllumUp = SDL_Scancode::SDL_SCANCODE_KBDILLUMUP as i32,
+    Eject = SDL_Scancode::SDL_SCANCODE_EJECT as i32,
+    Sleep = SDL_Scancode::SDL_SCANCODE_SLEEP as i32,
+    App1 = SDL_Scancode::SDL_SCANCODE_APP1 as i32,
+    App2 = SDL_Scancode::SDL_SCANCODE_APP2 as i32,
 }
+
+const SCANCODES: [Scancode; 240] = [
+    Scancode::A,
+    Scancode::B,
+    Scancode::C,
+    Scancode::D,
+    Scancode::E,
+    Scancode::F,
+    Scancode::G,
+    Scancode::H,
+    Scancode::I,
+    Scancode::J,
+    Scancode::K,
+    Scancode::L,
+    Scancode::M,
+    Scancode::N,
+    Scancode::O,
+    Scancode::P,
+    Scancode::Q,
+    Scancode::R,
+    Scancode::S,
+    Scancode::T,
+    Scancode::U,
+    Scancode::V,
+    Scancode::W,
+    Scancode::X,
+    Scancode::Y,
+    Scancode::Z,
+    Scancode::Num1,
+    Scancode::Num2,
+    Scancode::Num3,
+    Scancode::Num4,
+    Scancode::Num5,
+    Scancode::Num6,
+    Scancode::Num7,
+    Scancode::Num8,
+    Scancode::Num9,
+    Scancode::Num0,
+    Scancode::Return,
+    Scancode::Escape,
+    Scancode::Backspace,
+    Scancode::Tab,
+    Scancode::Space,
+    Scancode::Minus,
+    Scancode::Equals,
+    Scancode::LeftBracket,
+    Scancode::RightBracket,
+    Scancode::Backslash,
+    Scancode::NonUsHash,
+    Scancode::Semicolon,
+    Scancode::Apostrophe,
+    Scancode::Grave,
+    Scancode::Comma,
+    Scancode::Period,
+    Scancode::Slash,
+    Scancode::CapsLock,
+    Scancode::F1,
+    Scancode::F2,
+    Scancode::F3,
+    Scancode::F4,
+    Scancode::F5,
+    Scancode::F6,
+    Scancode::F7,
+    Scancode::F8,
+    Scancode::F9,
+    Scancode::F10,
+    Scancode::F11,
+    Scancode::F12,
+    Scancode::PrintScreen,
+    Scancode::ScrollLock,
+    Scancode::Pause,
+    Scancode::Insert,
+    Scancode::Home,
+    Scancode::PageUp,
+    Scancode::Delete,
+    Scancode::End,
+    Scancode::PageDown,
+    Scancode::Right,
+    Scancode::Left,
+    Scancode::Down,
+    Scancode::Up,
+    Scancode::NumLockClear,
+    Scancode::KpDivide,
+    Scancode::KpMultiply,
+    Scancode::KpMinus,
+    Scancode::KpPlus,
+    Scancode::KpEnter,
+    Scancode::Kp1,
+    Scancode::Kp2,
+    Scancode::Kp3,
+    Scancode::Kp4,
+    Scancode::Kp5,
+    Scancode::Kp6,
+    Scancode::Kp7,
+    Scancode::Kp8,
+    Scancode::Kp9,
+    Scancode::Kp0,
+    Scancode::KpPeriod,
+    Scancode::NonUsBackslash,
+    Scancode::Application,
+    Scancode::Power,
+    Scancode::KpEquals,
+    Scancode::F13,
+    Scancode::F14,
+    Scancode::F15,
+    Scancode::F16,
+    Scancode::F17,
+    Scancode::F18,
+    Scancode::F19,
+    Scancode::F20,
+    Scancode::F21,
+    Scancode::F22,
+    Scancode::F23,
+    Scancode::F24,
+    Scancode::Execute,
+    Scancode::Help,
+    Scancode::Menu,
+    Scancode::Select,
+    Scancode::Stop,
+    Scancode::Again,
+    Scancode::Undo,
+    Scancode::Cut,
+    Scancode::Copy,
+    Scancode::Paste,
+    Scancode::Find,
+    Scancode::Mute,
+    Scancode::VolumeUp,
+    Scancode::VolumeDown,
+    Scancode::KpComma,
+    Scancode::KpEqualsAS400,
+    Scancode::International1,
+    Scancode::International2,
+    Scancode::International3,
+    Scancode::International4,
+    Scancode::International5,
+    Scancode::International6,
+    Scancode::International7,
+    Scancode::International8,
+    Scancode::International9,
+    Scancode::Lang1,
+    Scancode::Lang2,
+    Scancode::Lang3,
+    Scancode::Lang4,
+    Scancode::Lang5,
+    Scancode::Lang6,
+    Scancode::Lang7,
+    Scancode::Lang8,
+    Scancode::Lang9,
+    Scancode::AltErase,
+    Scancode::SysReq,
+    Scancode::Cancel,
+    Scancode::Clear,
+    Scancode::Prior,
+    Scancode::Return2,
+    Scancode::Separator,
+    Scancode::Out,
+    Scancode::Oper,
+    Scancode::ClearAgain,
+    Scancode::CrSel,
+    Scancode::ExSel,
+    Scancode::Kp00,
+    Scancode::Kp000,
+    Scancode::ThousandsSeparator,
+    Scancode::DecimalSeparator,
+    Scancode::CurrencyUnit,
+    Scancode::CurrencySubUnit,
+    Scancode::KpLeftParen,
+    Scancode::KpRightParen,
+    Scancode::KpLeftBrace,
+    Scancode::KpRightBrace,
+    Scancode::KpTab,
+    Scancode::KpBackspace,
+    Scancode::KpA,
+    Scancode::KpB,
+    Scancode::KpC,
+    Scancode::KpD,
+    Scancode::KpE,
+    Scancode::KpF,
+    Scancode::KpXor,
+    Scancode::KpPower,
+    Scancode::KpPercent,
+    Scancode::KpLess,
+    Scancode::KpGreater,
+    Scancode::KpAmpersand,
+    Scancode::KpDblAmpersand,
+    Scancode::KpVerticalBar,
+    Scancode::KpDblVerticalBar,
+    Scancode::KpColon,
+    Scancode::KpHash,
+    Scancode::KpSpace,
+    Scancode::KpAt,
+    Scancode::KpExclam,
+    Scancode::KpMemStore,
+    Scancode::KpMemRecall,
+    Scancode::KpMemClear,
+    Scancode::KpMemAdd,
+    Scancode::KpMemSubtract,
+    Scancode::KpMemMultiply,
+    Scancode::KpMemDivide,
+    Scancode::KpPlusMinus,
+    Scancode::KpClear,
+    Scancode::KpClearEntry,
+    Scancode::KpBinary,
+    Scancode::KpOctal,
+    Scancode::KpDecimal,
+    Scancode::KpHexadecimal,
+    Scancode::LCtrl,
+    Scancode::LShift,
+    Scancode::LAlt,
+    Scancode::LGui,
+    Scancode::RCtrl,
+    Scancode::RShift,
+    Scancode::RAlt,
+    Scancode::RGui,
+    Scancode::Mode,
+    Scancode::AudioNext,
+    Scancode::AudioPrev,
+    Scancode::AudioStop,
+    Scancode::AudioPlay,
+    Scancode::AudioMute,
+    Scancode::MediaSelect,
+    Scancode::Www,
+    Scancode::Mail,
+    Scancode::Calculator,
+    Scancode::Computer,
+    Scancode::AcSearch,
+    Scancode::AcHome,
+    Scancode::AcBack,
+    Scancode::AcForward,
+    Scancode::AcStop,
+    Scancode::AcRefresh,
+    Scancode::AcBookmarks,
+    Scancode::BrightnessDown,
+    Scancode::BrightnessUp,
+    Scancode::DisplaySwitch,
+    Scancode::KbdIllumToggle,
+    Scancode::KbdIllumDown,
+    Scancode::KbdIllumUp,
+    Scancode::Eject,
+    Scancode::Sleep,
+    Scancode::App1,
+    Scancode::App2,
+];
+
+/// Every `Scancode` at the index of its SDL value, so converting from SDL doesn't search `SCANCODES`
+static SCANCODES_BY_VALUE: [Scancode; SDL_Scancode::SDL_NUM_SCANCODES as usize] = {
+    let mut table = [Scancode::Unknown; SDL_Scancode::SDL_NUM_SCANCODES as usize];
+    let mut i = 0;
+    while i < SCANCODES.len() {
+        table[SCANCODES[i] as usize] = SCANCODES[i];
+        i += 1;
+    }
+    table
+};
+
+/// A key by what it types on the current keyboard layout, so `Keycode::Z` is where Z is printed
+#[repr(i32)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Keycode {
+    Unknown = SDL_KeyCode::SDLK_UNKNOWN as i32,
+    Return = SDL_KeyCode::SDLK_RETURN as i32,
+    Escape = SDL_KeyCode::SDLK_ESCAPE as i32,
+    Backspace = SDL_KeyCode::SDLK_BACKSPACE as i32,
+    Tab = SDL_KeyCode::SDLK_TAB as i32,
+    Space = SDL_KeyCode::SDLK_SPACE as i32,
+    Exclaim = SDL_KeyCode::SDLK_EXCLAIM as i32,
+    QuoteDbl = SDL_KeyCode::SDLK_QUOTEDBL as i32,
+    Hash = SDL_KeyCode::SDLK_HASH as i32,
+    Percent = SDL_KeyCode::SDLK_PERCENT as i32,
+    Dollar = SDL_KeyCode::SDLK_DOLLAR as i32,
+    Ampersand = SDL_KeyCode::SDLK_AMPERSAND as i32,
+    Apostrophe = SDL_KeyCode::SDLK_QUOTE as i32,
+    LeftParen = SDL_KeyCode::SDLK_LEFTPAREN as i32,
+    RightParen = SDL_KeyCode::SDLK_RIGHTPAREN as i32,
+    Asterisk = SDL_KeyCode::SDLK_ASTERISK as i32,
+    Plus = SDL_KeyCode::SDLK_PLUS as i32,
+    Comma = SDL_KeyCode::SDLK_COMMA as i32,
+    Minus = SDL_KeyCode::SDLK_MINUS as i32,
+    Period = SDL_KeyCode::SDLK_PERIOD as i32,
+    Slash = SDL_KeyCode::SDLK_SLASH as i32,
+    Num0 = SDL_KeyCode::SDLK_0 as i32,
+    Num1 = SDL_KeyCode::SDLK_1 as i32,
+    Num2 = SDL_KeyCode::SDLK_2 as i32,
+    Num3 = SDL_KeyCode::SDLK_3 as i32,
+    Num4 = SDL_KeyCode::SDLK_4 as i32,
+    Num5 = SDL_KeyCode::SDLK_5 as i32,
+    Num6 = SDL_KeyCode::SDLK_6 as i32,
+    Num7 = SDL_KeyCode::SDLK_7 as i32,
+    Num8 = SDL_KeyCode::SDLK_8 as i32,
+    Num9 = SDL_KeyCode::SDLK_9 as i32,
+    Colon = SDL_KeyCode::SDLK_COLON as i32,
+    Semicolon = SDL_KeyCode::SDLK_SEMICOLON as i32,
+    Less = SDL_KeyCode::SDLK_LESS as i32,
+    Equals = SDL_KeyCode::SDLK_EQUALS as i32,
+    Greater = SDL_KeyCode::SDLK_GREATER as i32,
+    Question = SDL_KeyCode::SDLK_QUESTION as i32,
+    At = SDL_KeyCode::SDLK_AT as i32,
+    LeftBracket = SDL_KeyCode::SDLK_LEFTBRACKET as i32,
+    Backslash = SDL_KeyCode::SDLK_BACKSLASH as i32,
+    RightBracket = SDL_KeyCode::SDLK_RIGHTBRACKET as i32,
+    Caret = SDL_KeyCode::SDLK_CARET as i32,
+    Underscore = SDL_KeyCode::SDLK_UNDERSCORE as i32,
+    Grave = SDL_KeyCode::SDLK_BACKQUOTE as i32,
+    A = SDL_KeyCode::SDLK_a as i32,
+    B = SDL_KeyCode::SDLK_b as i32,
+    C = SDL_KeyCode::SDLK_c as i32,
+    D = SDL_KeyCode::SDLK_d as i32,
+    E = SDL_KeyCode::SDLK_e as i32,
+    F = SDL_KeyCode::SDLK_f as i32,
+    G = SDL_KeyCode::SDLK_g as i32,
+    H = SDL_KeyCode::SDLK_h as i32,
+    I = SDL_KeyCode::SDLK_i as i32,
+    J = SDL_KeyCode::SDLK_j as i32,
+    K = SDL_KeyCode::SDLK_k as i32,
+    L = SDL_KeyCode::SDLK_l as i32,
+    M = SDL_KeyCode::SDLK_m as i32,
+    N = SDL_KeyCode::SDLK_n as i32,
+    O = SDL_KeyCode::SDLK_o as i32,
+    P = SDL_KeyCode::SDLK_p as i32,
+    Q = SDL_KeyCode::SDLK_q as i32,
+    R = SDL_KeyCode::SDLK_r as i32,
+    S = SDL_KeyCode::SDLK_s as i32,
+    T = SDL_KeyCode::SDLK_t as i32,
+    U = SDL_KeyCode::SDLK_u as i32,
+    V = SDL_KeyCode::SDLK_v as i32,
+    W = SDL_KeyCode::SDLK_w as i32,
+    X = SDL_KeyCode::SDLK_x as i32,
+    Y = SDL_KeyCode::SDLK_y as i32,
+    Z = SDL_KeyCode::SDLK_z as i32,
+    CapsLock = SDL_KeyCode::SDLK_CAPSLOCK as i32,
+    F1 = SDL_KeyCode::SDLK_F1 as i32,
+    F2 = SDL_KeyCode::SDLK_F2 as i32,
+    F3 = SDL_KeyCode::SDLK_F3 as i32,
+    F4 = SDL_KeyCode::SDLK_F4 as i32,
+    F5 = SDL_KeyCode::SDLK_F5 as i32,
+    F6 = SDL_KeyCode::SDLK_F6 as i32,
+    F7 = SDL_KeyCode::SDLK_F7 as i32,
+    F8 = SDL_KeyCode::SDLK_F8 as i32,
+    F9 = SDL_KeyCode::SDLK_F9 as i32,
+    F10 = SDL_KeyCode::SDLK_F10 as i32,
+    F11 = SDL_KeyCode::SDLK_F11 as i32,
+    F12 = SDL_KeyCode::SDLK_F12 as i32,
+    PrintScreen = SDL_KeyCode::SDLK_PRINTSCREEN as i32,
+    ScrollLock = SDL_KeyCode::SDLK_SCROLLLOCK as i32,
+    Pause = SDL_KeyCode::SDLK_PAUSE as i32,
+    Insert = SDL_KeyCode::SDLK_INSERT as i32,
+    Home = SDL_KeyCode::SDLK_HOME as i32,
+    PageUp = SDL_KeyCode::SDLK_PAGEUP as i32,
+    Delete = SDL_KeyCode::SDLK_DELETE as i32,
+    End = SDL_KeyCode::SDLK_END as i32,
+    PageDown = SDL_KeyCode::SDLK_PAGEDOWN as i32,
+    Right = SDL_KeyCode::SDLK_RIGHT as i32,
+    Left = SDL_KeyCode::SDLK_LEFT as i32,
+    Down = SDL_KeyCode::SDLK_DOWN as i32,
+    Up = SDL_KeyCode::SDLK_UP as i32,
+    NumLockClear = SDL_KeyCode::SDLK_NUMLOCKCLEAR as i32,
+    KpDivide = SDL_KeyCode::SDLK_KP_DIVIDE as i32,
+    KpMultiply = SDL_KeyCode::SDLK_KP_MULTIPLY as i32,
+    KpMinus = SDL_KeyCode::SDLK_KP_MINUS as i32,
+    KpPlus = SDL_KeyCode::SDLK_KP_PLUS as i32,
+    KpEnter = SDL_KeyCode::SDLK_KP_ENTER as i32,
+    Kp1 = SDL_KeyCode::SDLK_KP_1 as i32,
+    Kp2 = SDL_KeyCode::SDLK_KP_2 as i32,
+    Kp3 = SDL_KeyCode::SDLK_KP_3 as i32,
+    Kp4 = SDL_KeyCode::SDLK_KP_4 as i32,
+    Kp5 = SDL_KeyCode::SDLK_KP_5 as i32,
+    Kp6 = SDL_KeyCode::SDLK_KP_6 as i32,
+    Kp7 = SDL_KeyCode::SDLK_KP_7 as i32,
+    Kp8 = SDL_KeyCode::SDLK_KP_8 as i32,
+    Kp9 = SDL_KeyCode::SDLK_KP_9 as i32,
+    Kp0 = SDL_KeyCode::SDLK_KP_0 as i32,
+    KpPeriod = SDL_KeyCode::SDLK_KP_PERIOD as i32,
+    Application = SDL_KeyCode::SDLK_APPLICATION as i32,
+    Power = SDL_KeyCode::SDLK_POWER as i32,
+    KpEquals = SDL_KeyCode::SDLK_KP_EQUALS as i32,
+    F13 = SDL_KeyCode::SDLK_F13 as i32,
+    F14 = SDL_KeyCode::SDLK_F14 as i32,
+    F15 = SDL_KeyCode::SDLK_F15 as i32,
+    F16 = SDL_KeyCode::SDLK_F16 as i32,
+    F17 = SDL_KeyCode::SDLK_F17 as i32,
+    F18 = SDL_KeyCode::SDLK_F18 as i32,
+    F19 = SDL_KeyCode::SDLK_F19 as i32,
+    F20 = SDL_KeyCode::SDLK_F20 as i32,
+    F21 = SDL_KeyCode::SDLK_F21 as i32,
+    F22 = SDL_KeyCode::SDLK_F22 as i32,
+    F23 = SDL_KeyCode::SDLK_F23 as i32,
+    F24 = SDL_KeyCode::SDLK_F24 as i32,
+    Execute = SDL_KeyCode::SDLK_EXECUTE as i32,
+    Help = SDL_KeyCode::SDLK_HELP as i32,
+    Menu = SDL_KeyCode::SDLK_MENU as i32,
+    Select = SDL_KeyCode::SDLK_SELECT as i32,
+    Stop = SDL_KeyCode::SDLK_STOP as i32,
+    Again = SDL_KeyCode::SDLK_AGAIN as i32,
+    Undo = SDL_KeyCode::SDLK_UNDO as i32,
+    Cut = SDL_KeyCode::SDLK_CUT as i32,
+    Copy = SDL_KeyCode::SDLK_COPY as i32,
+    Paste = SDL_KeyCode::SDLK_PASTE as i32,
+    Find = SDL_KeyCode::SDLK_FIND as i32,
+    Mute = SDL_KeyCode::SDLK_MUTE as i32,
+    VolumeUp = SDL_KeyCode::SDLK_VOLUMEUP as i32,
+    VolumeDown = SDL_KeyCode::SDLK_VOLUMEDOWN as i32,
+    KpComma = SDL_KeyCode::SDLK_KP_COMMA as i32,
+    KpEqualsAS400 = SDL_KeyCode::SDLK_KP_EQUALSAS400 as i32,
+    AltErase = SDL_KeyCode::SDLK_ALTERASE as i32,
+    SysReq = SDL_KeyCode::SDLK_SYSREQ as i32,
+    Cancel = SDL_KeyCode::SDLK_CANCEL as i32,
+    Clear = SDL_KeyCode::SDLK_CLEAR as i32,
+    Prior = SDL_KeyCode::SDLK_PRIOR as i32,
+    Return2 = SDL_KeyCode::SDLK_RETURN2 as i32,
+    Separator = SDL_KeyCode::SDLK_SEPARATOR as i32,
+    Out = SDL_KeyCode::SDLK_OUT as i32,
+    Oper = SDL_KeyCode::SDLK_OPER as i32,
+    ClearAgain = SDL_KeyCode::SDLK_CLEARAGAIN as i32,
+    CrSel = SDL_KeyCode::SDLK_CRSEL as i32,
+    ExSel = SDL_KeyCode::SDLK_EXSEL as i32,
+    Kp00 = SDL_KeyCode::SDLK_KP_00 as i32,
+    Kp000 = SDL_KeyCode::SDLK_KP_000 as i32,
+    ThousandsSeparator = SDL_KeyCode::SDLK_THOUSANDSSEPARATOR as i32,
+    DecimalSeparator = SDL_KeyCode::SDLK_DECIMALSEPARATOR as i32,
+    CurrencyUnit = SDL_KeyCode::SDLK_CURRENCYUNIT as i32,
+    CurrencySubUnit = SDL_KeyCode::SDLK_CURRENCYSUBUNIT as i32,
+    KpLeftParen = SDL_KeyCode::SDLK_KP_LEFTPAREN as i32,
+    KpRightParen = SDL_KeyCode::SDLK_KP_RIGHTPAREN as i32,
+    KpLeftBrace = SDL_KeyCode::SDLK_KP_LEFTBRACE as i32,
+    KpRightBrace = SDL_KeyCode::SDLK_KP_RIGHTBRACE as i32,
+    KpTab = SDL_KeyCode::SDLK_KP_TAB as i32,
+    KpBackspace = SDL_KeyCode::SDLK_KP_BACKSPACE as i32,
+    KpA = SDL_KeyCode::SDLK_KP_A as i32,
+    KpB = SDL_KeyCode::SDLK_KP_B as i32,
+    KpC = SDL_KeyCode::SDLK_KP_C as i32,
+    KpD = SDL_KeyCode::SDLK_KP_D as i32,
+    KpE = SDL_KeyCode::SDLK_KP_E as i32,
+    KpF = SDL_KeyCode::SDLK_KP_F as i32,
+    KpXor = SDL_KeyCode::SDLK_KP_XOR as i32,
+    KpPower = SDL_KeyCode::SDLK_KP_POWER as i32,
+    KpPercent = SDL_KeyCode::SDLK_KP_PERCENT as i32,
+    KpLess = SDL_KeyCode::SDLK_KP_LESS as i32,
+    KpGreater = SDL_KeyCode::SDLK_KP_GREATER as i32,
+    KpAmpersand = SDL_KeyCode::SDLK_KP_AMPERSAND as i32,
+    KpDblAmpersand = SDL_KeyCode::SDLK_KP_DBLAMPERSAND as i32,
+    KpVerticalBar = SDL_KeyCode::SDLK_KP_VERTICALBAR as i32,
+    KpDblVerticalBar = SDL_KeyCode::SDLK_KP_DBLVERTICALBAR as i32,
+    KpColon = SDL_KeyCode::SDLK_KP_COLON as i32,
+    KpHash = SDL_KeyCode::SDLK_KP_HASH as i32,
+    KpSpace = SDL_KeyCode::SDLK_KP_SPACE as i32,
+    KpAt = SDL_KeyCode::SDLK_KP_AT as i32,
+    KpExclam = SDL_KeyCode::SDLK_KP_EXCLAM as i32,
+    KpMemStore = SDL_KeyCode::SDLK_KP_MEMSTORE as i32,
+    KpMemRecall = SDL_KeyCode::SDLK_KP_MEMRECALL as i32,
+    KpMemClear = SDL_KeyCode::SDLK_KP_MEMCLEAR as i32,
+    KpMemAdd = SDL_KeyCode::SDLK_KP_MEMADD as i32,
+    KpMemSubtract = SDL_KeyCode::SDLK_KP_MEMSUBTRACT as i32,
+    KpMemMultiply = SDL_KeyCode::SDLK_KP_MEMMULTIPLY as i32,
+    KpMemDivide = SDL_KeyCode::SDLK_KP_MEMDIVIDE as i32,
+    KpPlusMinus = SDL_KeyCode::SDLK_KP_PLUSMINUS as i32,
+    KpClear = SDL_KeyCode::SDLK_KP_CLEAR as i32,
+    KpClearEntry = SDL_KeyCode::SDLK_KP_CLEARENTRY as i32,
+    KpBinary = SDL_KeyCode::SDLK_KP_BINARY as i32,
+    KpOctal = SDL_KeyCode::SDLK_KP_OCTAL as i32,
+    KpDecimal = SDL_KeyCode::SDLK_KP_DECIMAL as i32,
+    KpHexadecimal = SDL_KeyCode::SDLK_KP_HEXADECIMAL as i32,
+    LCtrl = SDL_KeyCode::SDLK_LCTRL as i32,
+    LShift = SDL_KeyCode::SDLK_LSHIFT as i32,
+    LAlt = SDL_KeyCode::SDLK_LALT as i32,
+    LGui = SDL_KeyCode::SDLK_LGUI as i32,
+    RCtrl = SDL_KeyCode::SDLK_RCTRL as i32,
+    RShift = SDL_KeyCode::SDLK_RSHIFT as i32,
+    RAlt = SDL_KeyCode::SDLK_RALT as i32,
+    RGui = SDL_KeyCode::SDLK_RGUI as i32,
+    Mode = SDL_KeyCode::SDLK_MODE as i32,
+    AudioNext = SDL_KeyCode::SDLK_AUDIONEXT as i32,
+    AudioPrev = SDL_KeyCode::SDLK_AUDIOPREV as i32,
+    AudioStop = SDL_KeyCode::SDLK_AUDIOSTOP as i32,
+    AudioPlay = SDL_KeyCode::SDLK_AUDIOPLAY as i32,
+    AudioMute = SDL_KeyCode::SDLK_AUDIOMUTE as i32,
+    MediaSelect = SDL_KeyCode::SDLK_MEDIASELECT as i32,
+    Www = SDL_KeyCode::SDLK_WWW as i32,
+    Mail = SDL_KeyCode::SDLK_MAIL as i32,
+    Calculator = SDL_KeyCode::SDLK_CALCULATOR as i32,
+    Computer = SDL_KeyCode::SDLK_COMPUTER as i32,
+    AcSearch = SDL_KeyCode::SDLK_AC_SEARCH as i32,
+    AcHome = SDL_KeyCode::SDLK_AC_HOME as i32,
+    AcBack = SDL_KeyCode::SDLK_AC_BACK as i32,
+    AcForward = SDL_KeyCode::SDLK_AC_FORWARD as i32,
+    AcStop = SDL_KeyCode::SDLK_AC_STOP as i32,
+    AcRefresh = SDL_KeyCode::SDLK_AC_REFRESH as i32,
+    AcBookmarks = SDL_KeyCode::SDLK_AC_BOOKMARKS as i32,
+    BrightnessDown = SDL_KeyCode::SDLK_BRIGHTNESSDOWN as i32,
+    BrightnessUp = SDL_KeyCode::SDLK_BRIGHTNESSUP as i32,
+    DisplaySwitch = SDL_KeyCode::SDLK_DISPLAYSWITCH as i32,
+    KbdIllumToggle = SDL_KeyCode::SDLK_KBDILLUMTOGGLE as i32,
+    KbdIllumDown = SDL_KeyCode::SDLK_KBDILLUMDOWN as i32,
+    KbdIllumUp = SDL_KeyCode::SDLK_KBDILLUMUP as i32,
+    Eject = SDL_KeyCode::SDLK_EJECT as i32,
+    Sleep = SDL_KeyCode::SDLK_SLEEP as i32,
+    App1 = SDL_KeyCode::SDLK_APP1 as i32,
+    App2 = SDL_KeyCode::SDLK_APP2 as i32,
+    AudioRewind = SDL_KeyCode::SDLK_AUDIOREWIND as i32,
+    AudioFastForward = SDL_KeyCode::SDLK_AUDIOFASTFORWARD as i32,
+    SoftLeft = SDL_KeyCode::SDLK_SOFTLEFT as i32,
+    SoftRight = SDL_KeyCode::SDLK_SOFTRIGHT as i32,
+    Call = SDL_KeyCode::SDLK_CALL as i32,
+    EndCall = SDL_KeyCode::SDLK_ENDCALL as i32,
+}
+
+const KEYCODES: [Keycode; 243] = [
+    Keycode::Return,
+    Keycode::Escape,
+    Keycode::Backspace,
+    Keycode::Tab,
+    Keycode::Space,
+    Keycode::Exclaim,
+    Keycode::QuoteDbl,
+    Keycode::Hash,
+    Keycode::Percent,
+    Keycode::Dollar,
+    Keycode::Ampersand,
+    Keycode::Apostrophe,
+    Keycode::LeftParen,
+    Keycode::RightParen,
+    Keycode::Asterisk,
+    Keycode::Plus,
+    Keycode::Comma,
+    Keycode::Minus,
+    Keycode::Period,
+    Keycode::Slash,
+    Keycode::Num0,
+    Keycode::Num1,
+    Keycode::Num2,
+    Keycode::Num3,
+    Keycode::Num4,
+    Keycode::Num5,
+    Keycode::Num6,
+    Keycode::Num7,
+    Keycode::Num8,
+    Keycode::Num9,
+    Keycode::Colon,
+    Keycode::Semicolon,
+    Keycode::Less,
+    Keycode::Equals,
+    Keycode::Greater,
+    Keycode::Question,
+    Keycode::At,
+    Keycode::LeftBracket,
+    Keycode::Backslash,
+    Keycode::RightBracket,
+    Keycode::Caret,
+    Keycode::Underscore,
+    Keycode::Grave,
+    Keycode::A,
+    Keycode::B,
+    Keycode::C,
+    Keycode::D,
+    Keycode::E,
+    Keycode::F,
+    Keycode::G,
+    Keycode::H,
+    Keycode::I,
+    Keycode::J,
+    Keycode::K,
+    Keycode::L,
+    Keycode::M,
+    Keycode::N,
+    Keycode::O,
+    Keycode::P,
+    Keycode::Q,
+    Keycode::R,
+    Keycode::S,
+    Keycode::T,
+    Keycode::U,
+    Keycode::V,
+    Keycode::W,
+    Keycode::X,
+    Keycode::Y,
+    Keycode::Z,
+    Keycode::CapsLock,
+    Keycode::F1,
+    Keycode::F2,
+    Keycode::F3,
+    Keycode::F4,
+    Keycode::F5,
+    Keycode::F6,
+    Keycode::F7,
+    Keycode::F8,
+    Keycode::F9,
+    Keycode::F10,
+    Keycode::F11,
+    Keycode::F12,
+    Keycode::PrintScreen,
+    Keycode::ScrollLock,
+    Keycode::Pause,
+    Keycode::Insert,
+    Keycode::Home,
+    Keycode::PageUp,
+    Keycode::Delete,
+    Keycode::End,
+    Keycode::PageDown,
+    Keycode::Right,
+    Keycode::Left,
+    Keycode::Down,
+    Keycode::Up,
+    Keycode::NumLockClear,
+    Keycode::KpDivide,
+    Keycode::KpMultiply,
+    Keycode::KpMinus,
+    Keycode::KpPlus,
+    Keycode::KpEnter,
+    Keycode::Kp1,
+    Keycode::Kp2,
+    Keycode::Kp3,
+    Keycode::Kp4,
+    Keycode::Kp5,
+    Keycode::Kp6,
+    Keycode::Kp7,
+    Keycode::Kp8,
+    Keycode::Kp9,
+    Keycode::Kp0,
+    Keycode::KpPeriod,
+    Keycode::Application,
+    Keycode::Power,
+    Keycode::KpEquals,
+    Keycode::F13,
+    Keycode::F14,
+    Keycode::F15,
+    Keycode::F16,
+    Keycode::F17,
+    Keycode::F18,
+    Keycode::F19,
+    Keycode::F20,
+    Keycode::F21,
+    Keycode::F22,
+    Keycode::F23,
+    Keycode::F24,
+    Keycode::Execute,
+    Keycode::Help,
+    Keycode::Menu,
+    Keycode::Select,
+    Keycode::Stop,
+    Keycode::Again,
+    Keycode::Undo,
+    Keycode::Cut,
+    Keycode::Copy,
+    Keycode::Paste,
+    Keycode::Find,
+    Keycode::Mute,
+    Keycode::VolumeUp,
+    Keycode::VolumeDown,
+    Keycode::KpComma,
+    Keycode::KpEqualsAS400,
+    Keycode::AltErase,
+    Keycode::SysReq,
+    Keycode::Cancel,
+    Keycode::Clear,
+    Keycode::Prior,
+    Keycode::Return2,
+    Keycode::Separator,
+    Keycode::Out,
+    Keycode::Oper,
+    Keycode::ClearAgain,
+    Keycode::CrSel,
+    Keycode::ExSel,
+    Keycode::Kp00,
+    Keycode::Kp000,
+    Keycode::ThousandsSeparator,
+    Keycode::DecimalSeparator,
+    Keycode::CurrencyUnit,
+    Keycode::CurrencySubUnit,
+    Keycode::KpLeftParen,
+    Keycode::KpRightParen,
+    Keycode::KpLeftBrace,
+    Keycode::KpRightBrace,
+    Keycode::KpTab,
+    Keycode::KpBackspace,
+    Keycode::KpA,
+    Keycode::KpB,
+    Keycode::KpC,
+    Keycode::KpD,
+    Keycode::KpE,
+    Keycode::KpF,
+    Keycode::KpXor,
+    Keycode::KpPower,
+    Keycode::KpPercent,
+    Keycode::KpLess,
+    Keycode::KpGreater,
+    Keycode::KpAmpersand,
+    Keycode::KpDblAmpersand,
+    Keycode::KpVerticalBar,
+    Keycode::KpDblVerticalBar,
+    Keycode::KpColon,
+    Keycode::KpHash,
+    Keycode::KpSpace,
+    Keycode::KpAt,
+    Keycode::KpExclam,
+    Keycode::KpMemStore,
+    Keycode::KpMemRecall,
+    Keycode::KpMemClear,
+    Keycode::KpMemAdd,
+    Keycode::KpMemSubtract,
+    Keycode::KpMemMultiply,
+    Keycode::KpMemDivide,
+    Keycode::KpPlusMinus,
+    Keycode::KpClear,
+    Keycode::KpClearEntry,
+    Keycode::KpBinary,
+    Keycode::KpOctal,
+    Keycode::KpDecimal,
+    Keycode::KpHexadecimal,
+    Keycode::LCtrl,
+    Keycode::LShift,
+    Keycode::LAlt,
+    Keycode::LGui,
+    Keycode::RCtrl,
+    Keycode::RShift,
+    Keycode::RAlt,
+    Keycode::RGui,
+    Keycode::Mode,
+    Keycode::AudioNext,
+    Keycode::AudioPrev,
+    Keycode::AudioStop,
+    Keycode::AudioPlay,
+    Keycode::AudioMute,
+    Keycode::MediaSelect,
+    Keycode::Www,
+    Keycode::Mail,
+    Keycode::Calculator,
+    Keycode::Computer,
+    Keycode::AcSearch,
+    Keycode::AcHome,
+    Keycode::AcBack,
+    Keycode::AcForward,
+    Keycode::AcStop,
+    Keycode::AcRefresh,
+    Keycode::AcBookmarks,
+    Keycode::BrightnessDown,
+    Keycode::BrightnessUp,
+    Keycode::DisplaySwitch,
+    Keycode::KbdIllumToggle,
+    Keycode::KbdIllumDown,
+    Keycode::KbdIllumUp,
+    Keycode::Eject,
+    Keycode::Sleep,
+    Keycode::App1,
+    Keycode::App2,
+    Keycode::AudioRewind,
+    Keycode::AudioFastForward,
+    Keycode::SoftLeft,
+    Keycode::SoftRight,
+    Keycode::Call,
+    Keycode::EndCall,
+];
+
+/// Keycodes that type a character are that character, from 0 to 127
+const CHARACTER_KEYCODES: usize = 128;
+
+/// Every character `Keycode` at the index of its value, and every other one at the index of its scancode,
+/// so converting from SDL doesn't search `KEYCODES`
+static KEYCODES_BY_VALUE: ([Keycode; CHARACTER_KEYCODES], [Keycode; SDL_Scancode::SDL_NUM_SCANCODES as usize]) = {
+    let mut characters = [Keycode::Unknown; CHARACTER_KEYCODES];
+    let mut others = [Keycode::Unknown; SDL_Scancode::SDL_NUM_SCANCODES as usize];
+    let mut i = 0;
+    while i < KEYCODES.len() {
+        let value = KEYCODES[i] as i32 as u32;
+        if value & SDLK_SCANCODE_MASK == 0 {
+            characters[value as usize] = KEYCODES[i];
+        } else {
+            others[(value & !SDLK_SCANCODE_MASK) as usize] = KEYCODES[i];
+        }
+        i += 1;
+    }
+    (characters, others)
+};
+
+impl Scancode {
+    pub(crate) fn to_sdl(self) -> Option<keyboard::Scancode> {
+        keyboard::Scancode::from_i32(self as i32)
+    }
+
+    /// get what this key types on the current keyboard layout
+    pub fn keycode(self) -> Option<Keycode> {
+        keyboard::Keycode::from_scancode(self.to_sdl()?)
+            .map(Keycode::from)
+            .filter(|keycode| *keycode != Keycode::Unknown)
+    }
+
+    /// get a name for this key to show to players, like "Left Shift" or "Z", following the current layout
+    pub fn name(self) -> String {
+        match self.keycode() {
+            Some(keycode) => keycode.name(),
+            None => self.to_sdl().map(|scancode| scancode.name().to_string()).unwrap_or_default(),
+        }
+    }
+
+    /// find a key by the name `Scancode::name` gives it
+    pub fn from_name(name: &str) -> Option<Scancode> {
+        Keycode::from_name(name)
+            .and_then(Keycode::scancode)
+            .or_else(|| keyboard::Scancode::from_name(name).map(Scancode::from))
+    }
+}
+
+impl From<keyboard::Scancode> for Scancode {
+    fn from(value: keyboard::Scancode) -> Self {
+        SCANCODES_BY_VALUE
+            .get(value as i32 as usize)
+            .copied()
+            .unwrap_or(Scancode::Unknown)
+    }
+}
+
+/// Finds the physical key that types this keycode on the current layout, or `Unknown` if none does
 impl From<Keycode> for Scancode {
     fn from(value: Keycode) -> Self {
-        match value {
-            Keycode::A => Scancode::A,
-            Keycode::B => Scancode::B,
-            Keycode::C => Scancode::C,
-            Keycode::D => Scancode::D,
-            Keycode::E => Scancode::E,
-            Keycode::F => Scancode::F,
-            Keycode::G => Scancode::G,
-            Keycode::H => Scancode::H,
-            Keycode::I => Scancode::I,
-            Keycode::J => Scancode::J,
-            Keycode::K => Scancode::K,
-            Keycode::L => Scancode::L,
-            Keycode::M => Scancode::M,
-            Keycode::N => Scancode::N,
-            Keycode::O => Scancode::O,
-            Keycode::P => Scancode::P,
-            Keycode::Q => Scancode::Q,
-            Keycode::R => Scancode::R,
-            Keycode::S => Scancode::S,
-            Keycode::T => Scancode::T,
-            Keycode::U => Scancode::U,
-            Keycode::V => Scancode::V,
-            Keycode::W => Scancode::W,
-            Keycode::X => Scancode::X,
-            Keycode::Y => Scancode::Y,
-            Keycode::Z => Scancode::Z,
-            Keycode::Num1 => Scancode::Num1,
-            Keycode::Num2 => Scancode::Num2,
-            Keycode::Num3 => Scancode::Num3,
-            Keycode::Num4 => Scancode::Num4,
-            Keycode::Num5 => Scancode::Num5,
-            Keycode::Num6 => Scancode::Num6,
-            Keycode::Num7 => Scancode::Num7,
-            Keycode::Num8 => Scancode::Num8,
-            Keycode::Num9 => Scancode::Num9,
-            Keycode::Num0 => Scancode::Num0,
-            Keycode::Return => Scancode::Return,
-            Keycode::Escape => Scancode::Escape,
-            Keycode::Backspace => Scancode::Backspace,
-            Keycode::Tab => Scancode::Tab,
-            Keycode::Space => Scancode::Space,
-            Keycode::Minus => Scancode::Minus,
-            Keycode::Equals => Scancode::Equals,
-            Keycode::LeftBracket => Scancode::LeftBracket,
-            Keycode::RightBracket => Scancode::RightBracket,
-            Keycode::Backslash => Scancode::Backslash,
-            Keycode::NonUsHash => Scancode::NonUsHash,
-            Keycode::Semicolon => Scancode::Semicolon,
-            Keycode::Apostrophe => Scancode::Apostrophe,
-            Keycode::Grave => Scancode::Grave,
-            Keycode::Comma => Scancode::Comma,
-            Keycode::Period => Scancode::Period,
-            Keycode::Slash => Scancode::Slash,
-            Keycode::CapsLock => Scancode::CapsLock,
-            Keycode::F1 => Scancode::F1,
-            Keycode::F2 => Scancode::F2,
-            Keycode::F3 => Scancode::F3,
-            Keycode::F4 => Scancode::F4,
-            Keycode::F5 => Scancode::F5,
-            Keycode::F6 => Scancode::F6,
-            Keycode::F7 => Scancode::F7,
-            Keycode::F8 => Scancode::F8,
-            Keycode::F9 => Scancode::F9,
-            Keycode::F10 => Scancode::F10,
-            Keycode::F11 => Scancode::F11,
-            Keycode::F12 => Scancode::F12,
-            Keycode::F13 => Scancode::F13,
-            Keycode::F14 => Scancode::F14,
-            Keycode::F15 => Scancode::F15,
-            Keycode::F16 => Scancode::F16,
-            Keycode::F17 => Scancode::F17,
-            Keycode::F18 => Scancode::F18,
-            Keycode::F19 => Scancode::F19,
-            Keycode::F20 => Scancode::F20,
-            Keycode::F21 => Scancode::F21,
-            Keycode::F22 => Scancode::F22,
-            Keycode::F23 => Scancode::F23,
-            Keycode::F24 => Scancode::F24,
-            Keycode::Delete => Scancode::Delete,
-            Keycode::Right => Scancode::Right,
-            Keycode::Left => Scancode::Left,
-            Keycode::Down => Scancode::Down,
-            Keycode::Up => Scancode::Up,
-        }
+        value.scancode().unwrap_or(Scancode::Unknown)
+    }
+}
+
+impl fmt::Display for Scancode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Keycode {
+    fn to_sdl(self) -> Option<keyboard::Keycode> {
+        keyboard::Keycode::from_i32(self as i32)
+    }
+
+    /// get the physical key that types this on the current keyboard layout
+    pub fn scancode(self) -> Option<Scancode> {
+        keyboard::Scancode::from_keycode(self.to_sdl()?).map(Scancode::from)
+    }
+
+    /// get a name for this key to show to players, like "Left Shift" or "Z"
+    pub fn name(self) -> String {
+        self.to_sdl().map(keyboard::Keycode::name).unwrap_or_default()
+    }
+
+    /// find a key by the name `Keycode::name` gives it
+    pub fn from_name(name: &str) -> Option<Keycode> {
+        keyboard::Keycode::from_name(name)
+            .map(Keycode::from)
+            .filter(|keycode| *keycode != Keycode::Unknown)
+    }
+}
+
+impl From<keyboard::Keycode> for Keycode {
+    fn from(value: keyboard::Keycode) -> Self {
+        let value = value.into_i32() as u32;
+        let (characters, others) = &KEYCODES_BY_VALUE;
+        let keycode = if value & SDLK_SCANCODE_MASK == 0 {
+            characters.get(value as usize)
+        } else {
+            others.get((value & !SDLK_SCANCODE_MASK) as usize)
+        };
+        keycode.copied().unwrap_or(Keycode::Unknown)
+    }
+}
+
+impl fmt::Display for Keycode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
     }
 }